@@ -0,0 +1,105 @@
+//! compares the bit mask based `FullPermission` against the previous string slicing
+//! implementation, which is kept here only as the baseline.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use fmodeparser::FullPermission;
+use std::fmt::Write;
+
+/// the modes of a typical directory scan.
+const MODES: [u32; 6] = [0o100644, 0o100755, 0o040755, 0o120777, 0o041777, 0o104755];
+
+/// the previous `digit_to_permission` helper.
+fn legacy_digit_to_permission(num: &str) -> String {
+    match num {
+        "1" => "--x",
+        "2" => "-w-",
+        "3" => "-wx",
+        "4" => "r--",
+        "5" => "r-x",
+        "6" => "rw-",
+        "7" => "rwx",
+        _ => "---",
+    }
+    .to_string()
+}
+
+/// the previous `file_type_number_to_symbol` helper.
+fn legacy_file_type_number_to_symbol(file_type: &str) -> char {
+    match file_type {
+        "040" => 'd',
+        "120" => 'l',
+        "020" => 'c',
+        "060" => 'b',
+        "010" => 'p',
+        "140" => 's',
+        _ => '-',
+    }
+}
+
+/// the previous `FullPermission::new` followed by `to_string`.
+fn legacy_to_string(mode: u32) -> String {
+    let mode_oc = format!("{:06o}", mode);
+    let file_type = legacy_file_type_number_to_symbol(&mode_oc[0..=2]);
+    let user = legacy_digit_to_permission(&mode_oc[3..=3]);
+    let group = legacy_digit_to_permission(&mode_oc[4..=4]);
+    let other = legacy_digit_to_permission(&mode_oc[5..=5]);
+    format!("{file_type}{user}{group}{other}")
+}
+
+/// the previous `FullPermission::get_mode`, rebuilding the octal string from its parts.
+fn legacy_get_mode(mode: u32) -> u32 {
+    let mode_oc = format!("{:06o}", mode);
+    let (num, user, group, other) = (
+        &mode_oc[0..=2],
+        &mode_oc[3..=3],
+        &mode_oc[4..=4],
+        &mode_oc[5..=5],
+    );
+    u32::from_str_radix(&format!("{num}{user}{group}{other}"), 8).unwrap_or(0)
+}
+
+fn bench_display(c: &mut Criterion) {
+    let mut group = c.benchmark_group("new_and_display");
+    group.bench_function("string_slicing", |b| {
+        b.iter(|| {
+            for mode in MODES {
+                black_box(legacy_to_string(black_box(mode)));
+            }
+        })
+    });
+    group.bench_function("bit_masks", |b| {
+        let mut buffer = String::with_capacity(16);
+        b.iter(|| {
+            for mode in MODES {
+                buffer.clear();
+                let permission = FullPermission::new(black_box(mode)).unwrap();
+                write!(buffer, "{permission}").unwrap();
+                black_box(&buffer);
+            }
+        })
+    });
+    group.finish();
+}
+
+fn bench_get_mode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_mode");
+    group.bench_function("string_slicing", |b| {
+        b.iter(|| {
+            for mode in MODES {
+                black_box(legacy_get_mode(black_box(mode)));
+            }
+        })
+    });
+    group.bench_function("bit_masks", |b| {
+        b.iter(|| {
+            for mode in MODES {
+                let permission = FullPermission::new(black_box(mode)).unwrap();
+                black_box(permission.get_mode());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_display, bench_get_mode);
+criterion_main!(benches);
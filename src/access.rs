@@ -0,0 +1,207 @@
+//! This module contains the `access(2)` evaluation of a permission: which of read, write
+//! and execute a user identity is granted on a file, given the owner and the group of the
+//! file.
+//!
+
+use crate::{Class, FileType, FullPermission, FullPermissionError, PermSet};
+use std::fs::Metadata;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+
+/// the execute bits of the three classes.
+const EXECUTE_BITS: u32 = 0o111;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+/// the user and the groups a process acts as when the kernel checks its permissions.
+///
+/// # Example
+/// ```
+/// use fmodeparser::{Class, FullPermission, Identity, OwnedPermission, PermSet};
+/// # use fmodeparser::FullPermissionError;
+/// # fn main() -> Result<(), FullPermissionError> {
+///
+/// let file = OwnedPermission::new("-r--rw-r--".parse()?, 1000, 100);
+/// let owner = Identity::new(1000, 100);
+/// let member = Identity::new(1001, 1001).with_groups([100]);
+///
+/// // the owner class wins even when the group class is more permissive
+/// assert_eq!(owner.class_for(&file), Class::User);
+/// assert!(!owner.can(&file, PermSet::WRITE));
+/// assert_eq!(member.granted(&file), PermSet::READ | PermSet::WRITE);
+/// assert!(Identity::new(1002, 1002).can(&file, PermSet::READ));
+///
+/// // root reads and writes everything but only executes what someone can execute
+/// let root = Identity::new(0, 0);
+/// assert_eq!(root.granted(&file), PermSet::READ | PermSet::WRITE);
+/// let script = OwnedPermission::new(FullPermission::new(0o100701)?, 1000, 100);
+/// assert!(root.can(&script, "rwx".parse::<PermSet>()?));
+/// # Ok(())
+/// # }
+/// ```
+pub struct Identity {
+    uid: u32,
+    gid: u32,
+    groups: Vec<u32>,
+}
+
+impl Identity {
+    /// returns the identity of a user and its primary group, without supplementary groups.
+    pub fn new(uid: u32, gid: u32) -> Self {
+        Self {
+            uid,
+            gid,
+            groups: Vec::new(),
+        }
+    }
+    /// returns a copy with the given supplementary groups.
+    pub fn with_groups(mut self, groups: impl IntoIterator<Item = u32>) -> Self {
+        self.groups = groups.into_iter().collect();
+        self
+    }
+    /// reads the identity of the current process from `/proc/self/status`.
+    ///
+    /// the filesystem ids are used, they are the ones the kernel checks when a file is
+    /// opened and they follow the effective ids unless `setfsuid(2)` changed them.
+    pub fn current() -> Result<Self, FullPermissionError> {
+        Self::from_status_file(Path::new("/proc/self/status"))
+    }
+    /// reads the identity of any process from `/proc/<pid>/status`.
+    pub fn of_process(pid: u32) -> Result<Self, FullPermissionError> {
+        Self::from_status_file(&PathBuf::from(format!("/proc/{pid}/status")))
+    }
+    fn from_status_file(path: &Path) -> Result<Self, FullPermissionError> {
+        let invalid = |message: &str| {
+            let error = std::io::Error::new(std::io::ErrorKind::InvalidData, message.to_string());
+            FullPermissionError::io(path, error)
+        };
+        let content =
+            std::fs::read_to_string(path).map_err(|error| FullPermissionError::io(path, error))?;
+        let ids = |key: &str| -> Result<Vec<u32>, FullPermissionError> {
+            let line = content
+                .lines()
+                .find_map(|line| line.strip_prefix(key))
+                .ok_or_else(|| invalid(&format!("the status has no `{key}` line")))?;
+            line.split_whitespace()
+                .map(|id| {
+                    id.parse()
+                        .map_err(|_| invalid(&format!("the `{key}` line has an invalid id")))
+                })
+                .collect()
+        };
+        // the ids are the real, effective, saved and filesystem ones
+        let filesystem_id = |key: &str| -> Result<u32, FullPermissionError> {
+            ids(key)?
+                .get(3)
+                .copied()
+                .ok_or_else(|| invalid(&format!("the `{key}` line has less than four ids")))
+        };
+        Ok(Self {
+            uid: filesystem_id("Uid:")?,
+            gid: filesystem_id("Gid:")?,
+            groups: ids("Groups:")?,
+        })
+    }
+    /// returns the user id.
+    pub fn get_uid(&self) -> u32 {
+        self.uid
+    }
+    /// returns the primary group id.
+    pub fn get_gid(&self) -> u32 {
+        self.gid
+    }
+    /// returns the supplementary group ids.
+    pub fn get_groups(&self) -> &[u32] {
+        &self.groups
+    }
+    /// returns `true` for the superuser, who is not limited by the permission bits.
+    pub fn is_root(&self) -> bool {
+        self.uid == 0
+    }
+    /// returns `true` if the group is the primary group or one of the supplementary groups.
+    pub fn in_group(&self, gid: u32) -> bool {
+        self.gid == gid || self.groups.contains(&gid)
+    }
+    /// returns the class whose bits apply to the identity: the user class for the owner,
+    /// else the group class for a member of the group, else the other class.
+    pub fn class_for(&self, file: &OwnedPermission) -> Class {
+        if self.uid == file.uid {
+            Class::User
+        } else if self.in_group(file.gid) {
+            Class::Group
+        } else {
+            Class::Other
+        }
+    }
+    /// returns the permissions granted on the file.
+    ///
+    /// only the bits of [`class_for`](Identity::class_for) are read, so an owner without
+    /// write permission cannot write even if every other class can. root is granted read
+    /// and write, and execute when the file is a directory or any class can execute it.
+    pub fn granted(&self, file: &OwnedPermission) -> PermSet {
+        let permission = file.permission;
+        if self.is_root() {
+            let executable = permission.get_file_type() == FileType::Directory
+                || permission.get_mode() & EXECUTE_BITS != 0;
+            let mut perms = PermSet::READ | PermSet::WRITE;
+            perms.set(PermSet::EXECUTE, executable);
+            return perms;
+        }
+        PermSet::from_bits_truncate(permission[self.class_for(file)].get_partial_mode())
+    }
+    /// returns `true` if every permission is granted, like `access(2)` with `R_OK`, `W_OK`
+    /// and `X_OK`.
+    pub fn can(&self, file: &OwnedPermission, perms: impl Into<PermSet>) -> bool {
+        self.granted(file).contains(perms.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+/// a permission with the user and the group that own the file.
+pub struct OwnedPermission {
+    permission: FullPermission,
+    uid: u32,
+    gid: u32,
+}
+
+impl OwnedPermission {
+    /// returns the permission of a file owned by `uid` and the group `gid`.
+    pub fn new(permission: FullPermission, uid: u32, gid: u32) -> Self {
+        Self {
+            permission,
+            uid,
+            gid,
+        }
+    }
+    /// reads the permission and the owners of a file, the symbolic links are followed
+    /// like `access(2)` does.
+    pub fn of_path(path: impl AsRef<Path>) -> Result<Self, FullPermissionError> {
+        let path = path.as_ref();
+        let metadata =
+            std::fs::metadata(path).map_err(|error| FullPermissionError::io(path, error))?;
+        Self::try_from(&metadata)
+    }
+    /// returns the permission.
+    pub fn get_permission(&self) -> FullPermission {
+        self.permission
+    }
+    /// returns the id of the owner.
+    pub fn get_uid(&self) -> u32 {
+        self.uid
+    }
+    /// returns the id of the group.
+    pub fn get_gid(&self) -> u32 {
+        self.gid
+    }
+}
+
+impl TryFrom<&Metadata> for OwnedPermission {
+    type Error = FullPermissionError;
+
+    fn try_from(metadata: &Metadata) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            FullPermission::new(metadata.mode())?,
+            metadata.uid(),
+            metadata.gid(),
+        ))
+    }
+}
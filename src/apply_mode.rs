@@ -0,0 +1,256 @@
+//! This module contains the methods that write a [`FullPermission`](crate::FullPermission)
+//! back to the file system and the conversions to and from [`std::fs::Permissions`].
+//!
+
+use crate::{FileType, FullPermission, FullPermissionError};
+use std::ffi::CString;
+use std::fs::{File, Metadata, Permissions};
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+/// what to do when the file type stored in the permission is not the type of the file it
+/// is applied to.
+pub enum TypeMismatch {
+    /// returns a [`Policy`](FullPermissionError::Policy) error without changing the file.
+    #[default]
+    Refuse,
+    /// changes the file anyway and returns the mismatch as the warning of the
+    /// [`ApplyOutcome`].
+    Warn,
+    /// changes the file without checking its type.
+    Ignore,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+/// the options used by [`FullPermission::apply_to_with_options`].
+///
+/// by default symbolic links are followed like `chmod` does and a file type mismatch is
+/// refused.
+///
+/// # Example
+/// ```
+/// use fmodeparser::{ApplyOptions, TypeMismatch};
+///
+/// let options = ApplyOptions::new()
+///     .no_follow(true)
+///     .on_type_mismatch(TypeMismatch::Warn);
+/// assert!(options.get_no_follow());
+/// ```
+pub struct ApplyOptions {
+    no_follow: bool,
+    type_mismatch: TypeMismatch,
+}
+
+impl ApplyOptions {
+    /// returns the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// when `true` a symbolic link is changed itself instead of the file it points to, most
+    /// systems (Linux included) do not support this and report an error for links.
+    pub fn no_follow(mut self, no_follow: bool) -> Self {
+        self.no_follow = no_follow;
+        self
+    }
+    /// sets what to do when the file type does not match, see [`TypeMismatch`].
+    pub fn on_type_mismatch(mut self, type_mismatch: TypeMismatch) -> Self {
+        self.type_mismatch = type_mismatch;
+        self
+    }
+    /// returns `true` if symbolic links are not followed.
+    pub fn get_no_follow(&self) -> bool {
+        self.no_follow
+    }
+    /// returns what is done when the file type does not match.
+    pub fn get_type_mismatch(&self) -> TypeMismatch {
+        self.type_mismatch
+    }
+}
+
+#[derive(Debug, Default)]
+/// what happened when a permission was applied with [`ApplyOptions`].
+///
+/// # Example
+/// ```
+/// use fmodeparser::{ApplyOptions, FullPermission, TypeMismatch};
+/// # use fmodeparser::FullPermissionError;
+/// # fn main() -> Result<(), FullPermissionError> {
+/// # let directory = tempfile::tempdir()?;
+///
+/// let options = ApplyOptions::new().on_type_mismatch(TypeMismatch::Warn);
+/// let outcome = FullPermission::new(0o100700)?.apply_to_with_options(directory.path(), options)?;
+/// let warning = outcome.get_warning().expect("a directory is not a regular file");
+/// assert!(matches!(warning, FullPermissionError::Policy { .. }));
+/// # Ok(())
+/// # }
+/// ```
+pub struct ApplyOutcome {
+    warning: Option<FullPermissionError>,
+}
+
+impl ApplyOutcome {
+    /// returns the [`Policy`](FullPermissionError::Policy) error that
+    /// [`TypeMismatch::Warn`] let through, if any.
+    pub fn get_warning(&self) -> Option<&FullPermissionError> {
+        self.warning.as_ref()
+    }
+    /// returns the warning and consumes the outcome.
+    pub fn into_warning(self) -> Option<FullPermissionError> {
+        self.warning
+    }
+}
+
+impl FullPermission {
+    /// changes the mode of the file at `path` to the permission and special bits, symbolic
+    /// links are followed like `chmod` does.
+    ///
+    /// the file type is not written but it must match the type of the file, otherwise
+    /// nothing is changed and a [`Policy`](FullPermissionError::Policy) error is returned.
+    /// a mode read without a file type, such as `755` or `u=rw,go=r`, is a regular file, it
+    /// is written to another kind of file with [`TypeMismatch::Ignore`].
+    ///
+    /// # Example
+    /// ```
+    /// use fmodeparser::{FullPermission, PermStrParser};
+    /// # use fmodeparser::FullPermissionError;
+    /// # fn main() -> Result<(), FullPermissionError> {
+    /// # let directory = tempfile::tempdir()?;
+    /// # let path = directory.path().join("script.sh");
+    /// # std::fs::write(&path, "")?;
+    ///
+    /// let (permission, _) = FullPermission::parse_any("u=rwx,go=rx")?;
+    /// permission.apply_to(&path)?;
+    /// assert_eq!(path.convert_permission_to_string()?, "-rwxr-xr-x");
+    ///
+    /// let directory_permission = permission.with_file_type(fmodeparser::FileType::Directory);
+    /// assert!(directory_permission.apply_to(&path).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_to(&self, path: impl AsRef<Path>) -> Result<(), FullPermissionError> {
+        self.apply_to_with_options(path, ApplyOptions::default())
+            .map(|_| ())
+    }
+    /// changes the mode of the file at `path` with the given [`ApplyOptions`], the outcome
+    /// holds the warning of [`TypeMismatch::Warn`].
+    pub fn apply_to_with_options(
+        &self,
+        path: impl AsRef<Path>,
+        options: ApplyOptions,
+    ) -> Result<ApplyOutcome, FullPermissionError> {
+        let path = path.as_ref();
+        let metadata = if options.no_follow {
+            path.symlink_metadata()
+        } else {
+            path.metadata()
+        }
+        .map_err(|error| FullPermissionError::io(path, error))?;
+        let warning = self.check_file_type(&metadata, options.type_mismatch, Some(path))?;
+        let mode = self.get_mode_bits().bits();
+        let result = if options.no_follow {
+            chmod_no_follow(path, mode)
+        } else {
+            std::fs::set_permissions(path, Permissions::from_mode(mode))
+        };
+        result.map_err(|error| FullPermissionError::io(path, error))?;
+        Ok(ApplyOutcome { warning })
+    }
+    /// changes the mode of an open file, the file type is checked like
+    /// [`apply_to`](FullPermission::apply_to) does.
+    pub fn apply_to_file(&self, file: &File) -> Result<(), FullPermissionError> {
+        self.apply_to_file_with_options(file, ApplyOptions::default())
+            .map(|_| ())
+    }
+    /// changes the mode of an open file with the given [`ApplyOptions`], `no_follow` has no
+    /// effect since an open file is never a link.
+    pub fn apply_to_file_with_options(
+        &self,
+        file: &File,
+        options: ApplyOptions,
+    ) -> Result<ApplyOutcome, FullPermissionError> {
+        let warning = self.check_file_type(&file.metadata()?, options.type_mismatch, None)?;
+        file.set_permissions(Permissions::from_mode(self.get_mode_bits().bits()))?;
+        Ok(ApplyOutcome { warning })
+    }
+    /// compares the stored file type with the type of the file that will be changed, a
+    /// mismatch is either an error or the returned warning.
+    fn check_file_type(
+        &self,
+        metadata: &Metadata,
+        type_mismatch: TypeMismatch,
+        path: Option<&Path>,
+    ) -> Result<Option<FullPermissionError>, FullPermissionError> {
+        let actual = FileType::from_bits(metadata.mode());
+        if actual == self.get_file_type() {
+            return Ok(None);
+        }
+        let message = format!(
+            "the permission is for a `{}` file but the file is a `{actual}` file",
+            self.get_file_type()
+        );
+        let mismatch = FullPermissionError::Policy {
+            path: path.map(Path::to_path_buf),
+            message,
+        };
+        match type_mismatch {
+            TypeMismatch::Refuse => Err(mismatch),
+            TypeMismatch::Warn => Ok(Some(mismatch)),
+            TypeMismatch::Ignore => Ok(None),
+        }
+    }
+}
+
+/// changes the mode of the path itself without following a final symbolic link.
+fn chmod_no_follow(path: &Path, mode: u32) -> io::Result<()> {
+    let path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+    // SAFETY: `path` is a valid nul terminated string that lives during the call.
+    let result = unsafe {
+        libc::fchmodat(
+            libc::AT_FDCWD,
+            path.as_ptr(),
+            mode as libc::mode_t,
+            libc::AT_SYMLINK_NOFOLLOW,
+        )
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// keeps only the permission and special bits, the file type is not part of a
+/// [`Permissions`].
+impl From<FullPermission> for Permissions {
+    fn from(permission: FullPermission) -> Self {
+        Permissions::from_mode(permission.get_mode_bits().bits())
+    }
+}
+
+impl From<&FullPermission> for Permissions {
+    fn from(permission: &FullPermission) -> Self {
+        Permissions::from(*permission)
+    }
+}
+
+/// the permissions read from a file keep its type bits, the ones made with
+/// `Permissions::from_mode` are read as a regular file when they have none.
+impl TryFrom<Permissions> for FullPermission {
+    type Error = FullPermissionError;
+
+    fn try_from(permissions: Permissions) -> Result<Self, Self::Error> {
+        FullPermission::new(permissions.mode())
+    }
+}
+
+impl TryFrom<&Permissions> for FullPermission {
+    type Error = FullPermissionError;
+
+    fn try_from(permissions: &Permissions) -> Result<Self, Self::Error> {
+        FullPermission::new(permissions.mode())
+    }
+}
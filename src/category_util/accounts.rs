@@ -0,0 +1,97 @@
+//! This module contains the lookup of the user and group names of the ids that own a file,
+//! it reads the account databases through the reentrant `getpwuid_r` and `getgrgid_r`.
+//!
+
+use std::ffi::{c_char, CStr};
+
+/// the buffer size used when the system does not give a hint.
+const DEFAULT_BUFFER_SIZE: usize = 1024;
+/// the largest buffer tried before the lookup gives up.
+const MAX_BUFFER_SIZE: usize = 1 << 20;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+/// an owner or a group as `ls -l` writes it, its name or its id when the name is unknown.
+pub struct Account {
+    /// the name, or the decimal id.
+    pub text: String,
+    /// `true` when `text` is a name, `ls` aligns the names on the left and the ids on the
+    /// right, and a name may be made of digits only.
+    pub resolved: bool,
+}
+
+impl Account {
+    /// returns the name of the user, or its id when it has no account.
+    pub fn user(uid: u32) -> Self {
+        user_name(uid).map_or_else(|| Self::id(uid), Self::name)
+    }
+    /// returns the name of the group, or its id when it does not exist.
+    pub fn group(gid: u32) -> Self {
+        group_name(gid).map_or_else(|| Self::id(gid), Self::name)
+    }
+    /// returns the id written as a number, like `ls -n` does.
+    pub fn id(id: u32) -> Self {
+        Self {
+            text: id.to_string(),
+            resolved: false,
+        }
+    }
+    fn name(name: String) -> Self {
+        Self {
+            text: name,
+            resolved: true,
+        }
+    }
+}
+
+/// returns the name of the user with the given id, or `None` if it has no account.
+pub fn user_name(uid: u32) -> Option<String> {
+    // SAFETY: a zeroed `passwd` is a valid value that is only read after a successful call.
+    let mut entry: libc::passwd = unsafe { std::mem::zeroed() };
+    lookup(libc::_SC_GETPW_R_SIZE_MAX, |buffer, result| {
+        // SAFETY: the buffer and the pointers are valid during the call.
+        let status =
+            unsafe { libc::getpwuid_r(uid, &mut entry, buffer.as_mut_ptr(), buffer.len(), result) };
+        (status, entry.pw_name)
+    })
+}
+
+/// returns the name of the group with the given id, or `None` if it does not exist.
+pub fn group_name(gid: u32) -> Option<String> {
+    // SAFETY: a zeroed `group` is a valid value that is only read after a successful call.
+    let mut entry: libc::group = unsafe { std::mem::zeroed() };
+    lookup(libc::_SC_GETGR_R_SIZE_MAX, |buffer, result| {
+        // SAFETY: the buffer and the pointers are valid during the call.
+        let status =
+            unsafe { libc::getgrgid_r(gid, &mut entry, buffer.as_mut_ptr(), buffer.len(), result) };
+        (status, entry.gr_name)
+    })
+}
+
+/// runs a reentrant lookup with a buffer that grows while it is too small, the call
+/// returns its status and the name field of the entry.
+fn lookup<T>(
+    size_hint: libc::c_int,
+    mut call: impl FnMut(&mut [c_char], *mut *mut T) -> (libc::c_int, *mut c_char),
+) -> Option<String> {
+    // SAFETY: `sysconf` has no preconditions.
+    let hint = unsafe { libc::sysconf(size_hint) };
+    let mut size = usize::try_from(hint)
+        .ok()
+        .filter(|&size| size > 0)
+        .unwrap_or(DEFAULT_BUFFER_SIZE);
+    loop {
+        let mut buffer: Vec<c_char> = vec![0; size];
+        let mut result: *mut T = std::ptr::null_mut();
+        let (status, name) = call(&mut buffer, &mut result);
+        if status == libc::ERANGE && size < MAX_BUFFER_SIZE {
+            size *= 2;
+            continue;
+        }
+        if status != 0 || result.is_null() || name.is_null() {
+            return None;
+        }
+        // SAFETY: the name points into `buffer` which is still alive and nul terminated.
+        let name = unsafe { CStr::from_ptr(name) };
+        return Some(name.to_string_lossy().into_owned());
+    }
+}
@@ -0,0 +1,10 @@
+//! This module contains the split of a device number into its major and minor numbers.
+//!
+
+/// returns the major and minor numbers of the device `rdev`, the type of `dev_t` and of
+/// the numbers differs between the systems so they are cast to the type of each one.
+#[allow(clippy::unnecessary_cast)]
+pub fn device_numbers(rdev: u64) -> (u32, u32) {
+    let rdev = rdev as libc::dev_t;
+    (libc::major(rdev) as u32, libc::minor(rdev) as u32)
+}
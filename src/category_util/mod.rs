@@ -1,6 +1,10 @@
 //! This module contains the utilities for parsing file permission.
 //!
 
+pub mod accounts;
+pub mod device;
+pub mod mode_bits;
 pub mod modeparser;
 pub mod owners;
+pub mod path_escape;
 pub mod permssion_conver_util;
@@ -0,0 +1,140 @@
+//! This module contains the integer-backed representation of a mode with its bit masks
+//! and the lookup tables used to convert it to and from its symbolic form without
+//! allocating.
+//!
+
+use crate::FullPermissionError;
+use bitflags::bitflags;
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord, Default)]
+    /// the permission and special bits of a mode (the lower 12 bits of `st_mode`).
+    ///
+    /// the file type bits are not part of it, see [`FileType`](crate::FileType).
+    pub struct ModeBits: u32 {
+        /// set-user-ID bit.
+        const SETUID = 0o4000;
+        /// set-group-ID bit.
+        const SETGID = 0o2000;
+        /// sticky (restricted deletion) bit.
+        const STICKY = 0o1000;
+        const USER_READ = 0o400;
+        const USER_WRITE = 0o200;
+        const USER_EXECUTE = 0o100;
+        const GROUP_READ = 0o040;
+        const GROUP_WRITE = 0o020;
+        const GROUP_EXECUTE = 0o010;
+        const OTHER_READ = 0o004;
+        const OTHER_WRITE = 0o002;
+        const OTHER_EXECUTE = 0o001;
+        /// all the user class bits.
+        const USER = 0o700;
+        /// all the group class bits.
+        const GROUP = 0o070;
+        /// all the other class bits.
+        const OTHER = 0o007;
+        /// the three special bits.
+        const SPECIAL = 0o7000;
+    }
+}
+
+/// keeps only the permission and special bits of a raw mode, the file type bits are dropped.
+impl From<u32> for ModeBits {
+    fn from(mode: u32) -> Self {
+        ModeBits::from_bits_truncate(mode)
+    }
+}
+
+/// the mask of the file type bits (`S_IFMT`).
+pub const FILE_TYPE_MASK: u32 = 0o170000;
+/// every mode must be lower than this value to fit in the 6 octal digits.
+pub const MODE_LIMIT: u32 = 0o1000000;
+
+/// the `rwx` triplet of every octal digit, indexed by the digit.
+pub const PERMISSION_SYMBOLS: [[char; 3]; 8] = [
+    ['-', '-', '-'],
+    ['-', '-', 'x'],
+    ['-', 'w', '-'],
+    ['-', 'w', 'x'],
+    ['r', '-', '-'],
+    ['r', '-', 'x'],
+    ['r', 'w', '-'],
+    ['r', 'w', 'x'],
+];
+
+/// parses the `rwx` triplet of a class that starts at `offset` inside the characters of
+/// `input` and returns its octal digit and whether the special bit of the class is set,
+/// `special_symbols` are the lowercase letters allowed in the execute slot: `s` for user
+/// and group, `t` for other, or `st` when the class is not known.
+///
+/// the error points at the offending character of the whole input, or at the end of the
+/// input when it is too short.
+pub fn parse_triplet(
+    input: &str,
+    chars: &[char],
+    offset: usize,
+    special_symbols: &str,
+) -> Result<(u32, bool), FullPermissionError> {
+    let mut execute_expected = String::from("x-");
+    for symbol in special_symbols.chars() {
+        execute_expected.push(symbol);
+        execute_expected.push(symbol.to_ascii_uppercase());
+    }
+    let mut digit = 0;
+    let mut special = false;
+    for (index, (bit, expected)) in [(0o4, "r-"), (0o2, "w-"), (0o1, execute_expected.as_str())]
+        .into_iter()
+        .enumerate()
+    {
+        let position = offset + index;
+        let symbol = match chars.get(position) {
+            Some(&symbol) if expected.contains(symbol) => symbol,
+            found => {
+                return Err(FullPermissionError::invalid_symbol(
+                    input,
+                    position,
+                    found.copied(),
+                    expected,
+                ))
+            }
+        };
+        if index == 2 && special_symbols.contains(symbol.to_ascii_lowercase()) {
+            special = true;
+        }
+        if matches!(symbol, 'r' | 'w' | 'x' | 's' | 't') {
+            digit |= bit;
+        }
+    }
+    Ok((digit, special))
+}
+
+/// parses the permission of a single class that starts at `offset` inside the characters
+/// of `input` and runs to its end, either a single octal digit like `6` or an `rwx` triplet
+/// like [`parse_triplet`] does.
+pub fn parse_class_permission(
+    input: &str,
+    chars: &[char],
+    offset: usize,
+    special_symbols: &str,
+) -> Result<(u32, bool), FullPermissionError> {
+    let (digit, special, end) = match chars.get(offset) {
+        Some(&symbol) if symbol.is_ascii_digit() => match symbol.to_digit(10) {
+            Some(digit) if digit <= 7 => (digit, false, offset + 1),
+            Some(digit) => return Err(FullPermissionError::InvalidDigit { digit }),
+            None => unreachable!("an ascii digit is always a decimal digit"),
+        },
+        _ => {
+            let (digit, special) = parse_triplet(input, chars, offset, special_symbols)?;
+            (digit, special, offset + 3)
+        }
+    };
+    if let Some(&found) = chars.get(end) {
+        return Err(FullPermissionError::invalid_symbol(
+            input,
+            end,
+            Some(found),
+            "",
+        ));
+    }
+    Ok((digit, special))
+}
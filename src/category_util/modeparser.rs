@@ -2,97 +2,139 @@
 //! representation and methods for complete manipulations!.
 //!
 
-use super::permssion_conver_util::permission_to_digit;
+use super::mode_bits::{parse_class_permission, PERMISSION_SYMBOLS};
+use crate::{FullPermissionError, Perm};
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
 /// This struct is used to store the individual characters!
 ///
-/// the struct methods are only used by OwnerKind enum !!
+/// the permission is kept as a single octal digit, the characters are looked up from it
+/// when they are requested.
 ///
-/// see [`OwnersKind`](crate::category_util::owners::OwnersKind) enum
+/// it can be parsed from an `rwx` triplet or a single octal digit, the `s`/`S`/`t`/`T`
+/// letters of the execute slot only tell whether execute is set since the special bits
+/// belong to the whole [`FullPermission`](crate::FullPermission).
+///
+/// see [`OwnersKind`](crate::OwnersKind) enum
+///
+/// # Example
+/// ```
+/// use fmodeparser::{ModeParser, Perm};
+/// # use fmodeparser::FullPermissionError;
+/// # fn main() -> Result<(), FullPermissionError> {
+///
+/// let mut mode_parser: ModeParser = "r-x".parse()?;
+/// assert_eq!(mode_parser.get_partial_mode(), 5);
+/// mode_parser.set_write(true);
+/// assert!(mode_parser.has(Perm::Write));
+/// assert_eq!(mode_parser, ModeParser::try_from("7")?);
+/// assert!("rwz".parse::<ModeParser>().is_err());
+/// # Ok(())
+/// # }
+/// ```
 pub struct ModeParser {
     partial_mode: u32,
-    read: char,
-    write: char,
-    execute: char,
 }
 
 impl ModeParser {
-    /// this is used after calling setter methods to reset the partial_mode so
-    /// that providing consistency when calling [`get_mode`]
-    ///
-    /// [`get_mode`]: crate::FullPermission::get_mode
-    fn reset_partial_mode(&mut self) {
-        let digit_of_permission = permission_to_digit(&self.to_string()).parse::<u32>();
-        self.set_partial_mode(digit_of_permission.unwrap());
+    /// sets or clears a single bit of the partial mode.
+    fn set_bit(&mut self, bit: u32, enabled: bool) {
+        if enabled {
+            self.partial_mode |= bit;
+        } else {
+            self.partial_mode &= !bit;
+        }
+    }
+    /// returns the character of the given slot (0 read, 1 write, 2 execute).
+    fn symbol(&self, slot: usize) -> char {
+        PERMISSION_SYMBOLS[self.partial_mode as usize & 0o7][slot]
     }
     pub fn set_partial_mode(&mut self, partial_mode: u32) {
-        self.partial_mode = partial_mode;
+        self.partial_mode = partial_mode & 0o7;
     }
     /// returns the partial mode which corresponds to either one of the three [`owners kind`]
     ///
-    /// [`owners kind`]: crate::category_util::owners::OwnersKind
+    /// [`owners kind`]: crate::OwnersKind
     ///
     pub fn get_partial_mode(&self) -> u32 {
         self.partial_mode
     }
-    /// this is used to set the read permission field with either `-` or `r`
-    pub fn set_read(&mut self, read: char) {
-        self.read = read;
-        self.reset_partial_mode();
+    /// this is used to set or clear the read permission field (`r` or `-`).
+    pub fn set_read(&mut self, read: bool) {
+        self.set_bit(0o4, read);
+    }
+    /// this is used to set or clear the write permission field (`w` or `-`).
+    pub fn set_write(&mut self, write: bool) {
+        self.set_bit(0o2, write);
     }
-    /// this is used to set the write permission field with either `-` or `w`
-    pub fn set_write(&mut self, write: char) {
-        self.write = write;
-        self.reset_partial_mode();
+    /// this is used to set or clear the execute permission field (`x` or `-`).
+    pub fn set_execute(&mut self, execute: bool) {
+        self.set_bit(0o1, execute);
     }
-    /// this is used to set the execute permission field with either `-` or `x`
-    pub fn set_execute(&mut self, execute: char) {
-        self.execute = execute;
-        self.reset_partial_mode();
+    /// sets or clears the given permission.
+    pub fn set(&mut self, perm: Perm, enabled: bool) {
+        self.set_bit(perm.bit(), enabled);
+    }
+    /// returns `true` if the given permission is set.
+    pub fn has(&self, perm: Perm) -> bool {
+        self.partial_mode & perm.bit() != 0
     }
     /// this is used to get the read permission field with either `-` or `r`
     pub fn get_read(&self) -> char {
-        self.read
+        self.symbol(0)
     }
     /// this is used to get the write permission field with either `-` or `w`
     pub fn get_write(&self) -> char {
-        self.write
+        self.symbol(1)
     }
     /// this is used to get the execute permission field with either `-` or `x`
     pub fn get_execute(&self) -> char {
-        self.execute
+        self.symbol(2)
     }
 }
 
-/// This trait will accept str or String of three characters such that for making
-/// new modeparser object
-impl From<&str> for ModeParser {
-    fn from(perm: &str) -> Self {
-        let read = perm.chars().next().unwrap_or('-');
-        let write = perm.chars().nth(1).unwrap_or('-');
-        let execute = perm.chars().nth(2).unwrap_or('-');
-        let mode = permission_to_digit(&format!("{}{}{}", read, write, execute))
-            .parse::<u32>()
-            .unwrap_or(0);
+impl From<u32> for ModeParser {
+    /// accepts a single octal digit, the bits above it are ignored.
+    fn from(partial_mode: u32) -> Self {
         Self {
-            partial_mode: mode,
-            read,
-            write,
-            execute,
+            partial_mode: partial_mode & 0o7,
         }
     }
 }
 
-impl From<String> for ModeParser {
-    fn from(perm: String) -> Self {
-        Self::from(perm.as_str())
+/// accepts an `rwx` triplet like `r-x` (with `s`/`S`/`t`/`T` in the execute slot) or a
+/// single octal digit like `5`.
+impl FromStr for ModeParser {
+    type Err = FullPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+        let (digit, _) = parse_class_permission(s, &chars, 0, "st")?;
+        Ok(Self::from(digit))
+    }
+}
+
+impl TryFrom<&str> for ModeParser {
+    type Error = FullPermissionError;
+
+    fn try_from(perm: &str) -> Result<Self, Self::Error> {
+        perm.parse()
+    }
+}
+
+impl TryFrom<String> for ModeParser {
+    type Error = FullPermissionError;
+
+    fn try_from(perm: String) -> Result<Self, Self::Error> {
+        perm.parse()
     }
 }
 
 impl Display for ModeParser {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{}{}", self.read, self.write, self.execute)
+        let [read, write, execute] = PERMISSION_SYMBOLS[self.partial_mode as usize & 0o7];
+        write!(f, "{read}{write}{execute}")
     }
 }
@@ -1,21 +1,64 @@
-use crate::ModeParser;
-use std::cell::RefCell;
+use super::mode_bits::parse_class_permission;
+use crate::{Class, FullPermissionError, ModeParser, Perm};
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use OwnersKind::*;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 /// this is a wrapper that encapsulate three kinds of owners and provide a nice interface
 /// to call the methods
+///
+/// it can be parsed from the class letter followed by `=` and the permission of the class
+/// like `u=rwx`, `g=r-s` or `o=4`.
+///
+/// # Example
+/// ```
+/// use fmodeparser::{Class, OwnersKind};
+/// # use fmodeparser::FullPermissionError;
+/// # fn main() -> Result<(), FullPermissionError> {
+///
+/// let mut group: OwnersKind = "g=r-x".parse()?;
+/// assert_eq!(group.class(), Class::Group);
+/// group.set_write(true);
+/// assert_eq!(group.to_string(), "rwx");
+/// assert!("o=rws".parse::<OwnersKind>().is_err());
+/// # Ok(())
+/// # }
+/// ```
 pub enum OwnersKind {
-    User(RefCell<ModeParser>),
-    Group(RefCell<ModeParser>),
-    Other(RefCell<ModeParser>),
+    User(ModeParser),
+    Group(ModeParser),
+    Other(ModeParser),
 }
 impl OwnersKind {
+    /// returns the owner kind of the given class.
+    pub fn new(class: Class, mode_parser: ModeParser) -> Self {
+        match class {
+            Class::User => User(mode_parser),
+            Class::Group => Group(mode_parser),
+            Class::Other => Other(mode_parser),
+        }
+    }
+    /// returns the class of the owner kind.
+    pub fn class(&self) -> Class {
+        match self {
+            User(_) => Class::User,
+            Group(_) => Class::Group,
+            Other(_) => Class::Other,
+        }
+    }
     /// returns the owner kind that will be used to access the mode parser methods.
     ///
     /// this is intended for internal usage not the end user .
-    fn get_mode_parser(&self) -> &RefCell<ModeParser> {
+    fn get_mode_parser(&self) -> &ModeParser {
+        match self {
+            User(user) => user,
+            Group(group) => group,
+            Other(other) => other,
+        }
+    }
+    /// returns the owner kind that will be used to modify the mode parser.
+    fn get_mode_parser_mut(&mut self) -> &mut ModeParser {
         match self {
             User(user) => user,
             Group(group) => group,
@@ -24,44 +67,91 @@ impl OwnersKind {
     }
     /// returns the character that represents the read permission of the owner either **`-`** or **`r`**.
     pub fn get_read(&self) -> char {
-        self.get_mode_parser().borrow().get_read()
+        self.get_mode_parser().get_read()
     }
     /// returns the character that represents the write permission of the owner either **`-`** or **`w`**.
-    pub fn get_write(&mut self) -> char {
-        self.get_mode_parser().borrow().get_write()
+    pub fn get_write(&self) -> char {
+        self.get_mode_parser().get_write()
     }
     /// returns the character that represents the execute permission of the owner either **`-`** or **`x`**.
-    pub fn get_execute(&mut self) -> char {
-        self.get_mode_parser().borrow().get_execute()
+    pub fn get_execute(&self) -> char {
+        self.get_mode_parser().get_execute()
+    }
+    /// sets or clears the read permission of the owner.
+    pub fn set_read(&mut self, read: bool) {
+        self.get_mode_parser_mut().set_read(read)
     }
-    /// assigns a new read permission to the owner.
-    pub fn set_read(&self, read: char) {
-        self.get_mode_parser().borrow_mut().set_read(read)
+    /// sets or clears the write permission of the owner.
+    pub fn set_write(&mut self, write: bool) {
+        self.get_mode_parser_mut().set_write(write)
     }
-    /// assigns a new write permission to the owner.
-    pub fn set_write(&self, write: char) {
-        self.get_mode_parser().borrow_mut().set_write(write)
+    /// sets or clears the execute permission of the owner.
+    pub fn set_execute(&mut self, execute: bool) {
+        self.get_mode_parser_mut().set_execute(execute)
     }
-    /// assigns a new execute permission to the owner.
-    pub fn set_execute(&self, execute: char) {
-        self.get_mode_parser().borrow_mut().set_execute(execute)
+    /// sets or clears the given permission of the owner.
+    pub fn set(&mut self, perm: Perm, enabled: bool) {
+        self.get_mode_parser_mut().set(perm, enabled)
+    }
+    /// returns `true` if the owner has the given permission.
+    pub fn has(&self, perm: Perm) -> bool {
+        self.get_mode_parser().has(perm)
     }
     /// returns the partial mode of the owner.
     pub fn get_partial_mode(&self) -> u32 {
-        self.get_mode_parser().borrow().get_partial_mode()
+        self.get_mode_parser().get_partial_mode()
+    }
+    /// assigns the whole permission of the owner from a single octal digit like `6` for `rw-`.
+    pub fn set_partial_mode(&mut self, partial_mode: u32) {
+        self.get_mode_parser_mut().set_partial_mode(partial_mode)
     }
 }
 impl Display for OwnersKind {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                User(user) => user,
-                Group(group) => group,
-                Other(other) => other,
+        write!(f, "{}", self.get_mode_parser())
+    }
+}
+
+/// accepts `u=`, `g=` or `o=` followed by an `rwx` triplet or a single octal digit, the
+/// execute slot only accepts the special letter of the class (`s`/`S` or `t`/`T`).
+impl FromStr for OwnersKind {
+    type Err = FullPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+        let (class, special_symbols) = match chars.first() {
+            Some('u') => (Class::User, "s"),
+            Some('g') => (Class::Group, "s"),
+            Some('o') => (Class::Other, "t"),
+            found => {
+                return Err(FullPermissionError::invalid_symbol(
+                    s,
+                    0,
+                    found.copied(),
+                    "ugo",
+                ))
             }
-            .borrow()
-        )
+        };
+        match chars.get(1) {
+            Some('=') => {}
+            found => {
+                return Err(FullPermissionError::invalid_symbol(
+                    s,
+                    1,
+                    found.copied(),
+                    "=",
+                ))
+            }
+        }
+        let (digit, _) = parse_class_permission(s, &chars, 2, special_symbols)?;
+        Ok(Self::new(class, ModeParser::from(digit)))
+    }
+}
+
+impl TryFrom<&str> for OwnersKind {
+    type Error = FullPermissionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
     }
 }
@@ -0,0 +1,39 @@
+//! This module contains the escaping of paths written in the line based text formats of
+//! the crate, it follows `getfacl`: every byte that is not a printable ASCII character, the
+//! space and the backslash are written as a `\ooo` octal escape.
+//!
+
+use std::ffi::OsString;
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::path::{Path, PathBuf};
+
+/// returns the path with every byte that could break a line or a field escaped.
+pub fn escape_path(path: &Path) -> String {
+    let mut escaped = String::new();
+    for &byte in path.as_os_str().as_bytes() {
+        if byte.is_ascii_graphic() && byte != b'\\' {
+            escaped.push(byte as char);
+        } else {
+            escaped.push_str(&format!("\\{byte:03o}"));
+        }
+    }
+    escaped
+}
+
+/// returns the path of an escaped string or `None` if an escape is not valid.
+pub fn unescape_path(escaped: &str) -> Option<PathBuf> {
+    let bytes = escaped.as_bytes();
+    let mut path = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] != b'\\' {
+            path.push(bytes[index]);
+            index += 1;
+            continue;
+        }
+        let digits = std::str::from_utf8(bytes.get(index + 1..index + 4)?).ok()?;
+        path.push(u8::from_str_radix(digits, 8).ok()?);
+        index += 4;
+    }
+    Some(PathBuf::from(OsString::from_vec(path)))
+}
@@ -0,0 +1,247 @@
+//! This module contains the transactional `chmod`: every original mode is written to a
+//! journal file before it is changed so a failed bulk change can be rolled back and a
+//! committed one can be undone later.
+//!
+//! the journal is a text file, the first line is a header, every change is a line with the
+//! original mode in octal and the escaped path, and a committed journal ends with a
+//! `commit` line:
+//!
+//! ```text
+//! # fmodeparser chmod journal
+//! 100644 /srv/app/config.toml
+//! 040755 /srv/app/bin
+//! commit
+//! ```
+//!
+
+use crate::category_util::path_escape::{escape_path, unescape_path};
+use crate::{ChmodReport, FullPermission, FullPermissionError, RecursiveChmod};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+
+/// the first line of every journal.
+const JOURNAL_HEADER: &str = "# fmodeparser chmod journal";
+/// the last line of a committed journal.
+const JOURNAL_COMMIT: &str = "commit";
+
+#[derive(Debug)]
+/// a set of permission changes that are either all kept or all undone.
+///
+/// the original mode of a file is written and synced to the journal before the file is
+/// changed. when a change fails every file changed before it is restored and the journal is
+/// removed, the same happens when the transaction is dropped without being committed.
+///
+/// # Example
+/// ```
+/// use fmodeparser::{ChmodTransaction, FullPermission, PermStrParser};
+/// # use fmodeparser::FullPermissionError;
+/// # fn main() -> Result<(), FullPermissionError> {
+/// # let directory = tempfile::tempdir()?;
+/// # let path = directory.path().join("config");
+/// # std::fs::write(&path, "")?;
+/// # FullPermission::new(0o100644)?.apply_to(&path)?;
+/// let journal = directory.path().join("chmod.journal");
+///
+/// let mut transaction = ChmodTransaction::begin(&journal)?;
+/// transaction.apply(&path, FullPermission::new(0o100600)?)?;
+/// transaction.commit()?;
+/// assert_eq!(path.convert_permission_to_string()?, "-rw-------");
+///
+/// ChmodTransaction::undo(&journal)?;
+/// assert_eq!(path.convert_permission_to_string()?, "-rw-r--r--");
+/// # Ok(())
+/// # }
+/// ```
+pub struct ChmodTransaction {
+    journal_path: PathBuf,
+    journal: File,
+    entries: Vec<(PathBuf, FullPermission)>,
+    finished: bool,
+}
+
+impl ChmodTransaction {
+    /// starts a transaction that writes its journal to `journal_path`, the file must not
+    /// exist yet so that an older journal is never overwritten.
+    pub fn begin(journal_path: impl Into<PathBuf>) -> Result<Self, FullPermissionError> {
+        let journal_path = journal_path.into();
+        let mut journal = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&journal_path)
+            .map_err(|error| FullPermissionError::io(&journal_path, error))?;
+        writeln!(journal, "{JOURNAL_HEADER}")
+            .and_then(|_| journal.sync_data())
+            .map_err(|error| FullPermissionError::io(&journal_path, error))?;
+        Ok(Self {
+            journal_path,
+            journal,
+            entries: Vec::new(),
+            finished: false,
+        })
+    }
+    /// returns the path of the journal.
+    pub fn get_journal_path(&self) -> &Path {
+        &self.journal_path
+    }
+    /// changes the mode of the file at `path` like [`FullPermission::apply_to`], the
+    /// transaction is rolled back if it fails.
+    pub fn apply(
+        &mut self,
+        path: impl AsRef<Path>,
+        permission: FullPermission,
+    ) -> Result<(), FullPermissionError> {
+        let path = path.as_ref();
+        self.check_open()?;
+        let result = path
+            .metadata()
+            .map_err(|error| FullPermissionError::io(path, error))
+            .and_then(|metadata| FullPermission::new(metadata.mode()))
+            .and_then(|before| self.record(path, before))
+            .and_then(|_| permission.apply_to(path));
+        result.or_else(|error| self.fail(error))
+    }
+    /// runs the recursive walker inside the transaction, the walk stops at the first error
+    /// and every change made by it is rolled back.
+    pub fn run(&mut self, walker: &RecursiveChmod) -> Result<ChmodReport, FullPermissionError> {
+        self.check_open()?;
+        let walker = walker.clone().continue_on_error(false);
+        let result = walker.run_with(|path, before| self.record(path, before));
+        result.or_else(|error| self.fail(error))
+    }
+    /// keeps the changes and marks the journal as committed, it returns the path of the
+    /// journal which can be given to [`undo`](ChmodTransaction::undo) later.
+    pub fn commit(mut self) -> Result<PathBuf, FullPermissionError> {
+        writeln!(self.journal, "{JOURNAL_COMMIT}")
+            .and_then(|_| self.journal.sync_data())
+            .map_err(|error| FullPermissionError::io(&self.journal_path, error))?;
+        self.finished = true;
+        Ok(self.journal_path.clone())
+    }
+    /// restores every file changed by the transaction and removes the journal.
+    pub fn rollback(mut self) -> Result<(), FullPermissionError> {
+        self.finished = true;
+        let failures = self.restore_all();
+        match failures.into_iter().next() {
+            Some(failure) => Err(failure),
+            None => self.remove_journal(),
+        }
+    }
+    /// restores the modes written in a journal, the last change first, so the files get
+    /// back the mode they had before the transaction.
+    ///
+    /// a journal that was not committed, because the process stopped in the middle of a
+    /// transaction, can be undone as well.
+    pub fn undo(journal_path: impl AsRef<Path>) -> Result<(), FullPermissionError> {
+        let journal_path = journal_path.as_ref();
+        let content = std::fs::read_to_string(journal_path)
+            .map_err(|error| FullPermissionError::io(journal_path, error))?;
+        let entries = parse_journal(&content)?;
+        // every file is restored even when an earlier one fails
+        let failures: Vec<_> = entries
+            .iter()
+            .rev()
+            .filter_map(|(path, permission)| permission.apply_to(path).err())
+            .collect();
+        match failures.into_iter().next() {
+            Some(failure) => Err(failure),
+            None => Ok(()),
+        }
+    }
+    /// returns an error when the transaction was already rolled back by a failed change.
+    fn check_open(&self) -> Result<(), FullPermissionError> {
+        if self.finished {
+            return Err(FullPermissionError::policy(
+                &self.journal_path,
+                "the transaction was already rolled back",
+            ));
+        }
+        Ok(())
+    }
+    /// writes the original mode of a file to the journal before it is changed, the path is
+    /// made absolute so the journal and the rollback work from any directory.
+    fn record(&mut self, path: &Path, before: FullPermission) -> Result<(), FullPermissionError> {
+        let absolute =
+            std::path::absolute(path).map_err(|error| FullPermissionError::io(path, error))?;
+        writeln!(
+            self.journal,
+            "{} {}",
+            before.mode_as_octal(),
+            escape_path(&absolute)
+        )
+        .and_then(|_| self.journal.sync_data())
+        .map_err(|error| FullPermissionError::io(&self.journal_path, error))?;
+        self.entries.push((absolute, before));
+        Ok(())
+    }
+    /// rolls back after a failed change and returns the error of the change.
+    fn fail<T>(&mut self, error: FullPermissionError) -> Result<T, FullPermissionError> {
+        self.finished = true;
+        let failures = self.restore_all();
+        if failures.is_empty() {
+            // the journal only lists restored files now, keeping it by mistake is harmless
+            let _ = self.remove_journal();
+            return Err(error);
+        }
+        Err(FullPermissionError::Rollback {
+            source: Box::new(error),
+            failures,
+        })
+    }
+    /// restores every recorded file, the last change first, and returns the failures.
+    fn restore_all(&mut self) -> Vec<FullPermissionError> {
+        self.entries
+            .drain(..)
+            .rev()
+            .filter_map(|(path, permission)| permission.apply_to(path).err())
+            .collect()
+    }
+    fn remove_journal(&self) -> Result<(), FullPermissionError> {
+        std::fs::remove_file(&self.journal_path)
+            .map_err(|error| FullPermissionError::io(&self.journal_path, error))
+    }
+}
+
+/// a transaction that was neither committed nor rolled back is rolled back, the errors
+/// are ignored and the journal is kept when a file could not be restored.
+impl Drop for ChmodTransaction {
+    fn drop(&mut self) {
+        if !self.finished && self.restore_all().is_empty() {
+            let _ = self.remove_journal();
+        }
+    }
+}
+
+/// returns the entries of a journal in the order they were written.
+fn parse_journal(content: &str) -> Result<Vec<(PathBuf, FullPermission)>, FullPermissionError> {
+    let mut lines = content.lines().enumerate();
+    match lines.next() {
+        Some((_, JOURNAL_HEADER)) => {}
+        first => {
+            return Err(FullPermissionError::InvalidLine {
+                line: 1,
+                input: first.map_or("", |(_, line)| line).to_string(),
+                reason: format!("a journal must start with `{JOURNAL_HEADER}`"),
+            })
+        }
+    }
+    let mut entries = Vec::new();
+    for (index, line) in lines {
+        if line == JOURNAL_COMMIT {
+            break;
+        }
+        let invalid = |reason: &str| FullPermissionError::InvalidLine {
+            line: index + 1,
+            input: line.to_string(),
+            reason: reason.to_string(),
+        };
+        let (mode, path) = line
+            .split_once(' ')
+            .ok_or_else(|| invalid("expected an octal mode and a path"))?;
+        let mode = u32::from_str_radix(mode, 8).map_err(|_| invalid("the mode is not octal"))?;
+        let path = unescape_path(path).ok_or_else(|| invalid("the path has an invalid escape"))?;
+        entries.push((path, FullPermission::new(mode)?));
+    }
+    Ok(entries)
+}
@@ -1,31 +1,283 @@
 use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::num::ParseIntError;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Eq, PartialEq)]
-
+#[derive(Debug)]
+#[non_exhaustive]
 /// the error that can be returned when parsing the permission.
-pub struct FullPermissionError {
-    message: String,
+///
+/// every kind of failure has its own variant so callers can tell an I/O failure from an
+/// out-of-range mode or a bad character, the wrapped errors are kept as the
+/// [`source`](Error::source) of the error.
+pub enum FullPermissionError {
+    /// the mode number does not fit in the 6 octal digits of a file mode.
+    InvalidMode { value: u32 },
+    /// the bits of a mode that is not a full file mode, such as a umask, are not all in the
+    /// `allowed` bits, `kind` names the value.
+    ModeOutOfRange {
+        kind: &'static str,
+        value: u32,
+        allowed: u32,
+    },
+    /// a class permission digit is not an octal digit between `0` and `7`.
+    InvalidDigit { digit: u32 },
+    /// a character of a parsed input is not allowed at the zero-based `position`, `found` is
+    /// `None` when the input ended too early and `expected` is empty when it should have ended.
+    InvalidSymbol {
+        input: String,
+        position: usize,
+        found: Option<char>,
+        expected: String,
+    },
+    /// a number could not be parsed in the given radix.
+    InvalidNumber {
+        input: String,
+        radix: u32,
+        source: ParseIntError,
+    },
+    /// a mode literal can be read both as octal and as decimal.
+    AmbiguousMode {
+        input: String,
+        octal: u32,
+        decimal: u32,
+    },
+    /// a line of a text format written by the crate, such as a journal, is not valid,
+    /// `line` starts at `1`.
+    InvalidLine {
+        line: usize,
+        input: String,
+        reason: String,
+    },
+    /// a format string, such as a `stat --format`, cannot be rendered, `position` is where
+    /// the problem starts.
+    InvalidFormat {
+        format: String,
+        position: usize,
+        reason: String,
+    },
+    /// a mode literal does not match any of the known forms.
+    UnrecognizedMode { input: String },
+    /// the file type bits are not known by the crate.
+    UnknownFileType { bits: u32 },
+    /// a `--time-style` of a listing is not one of the known styles or a `+FORMAT`.
+    InvalidTimeStyle { input: String },
+    /// a glob pattern used to select files is not valid.
+    InvalidGlob {
+        pattern: String,
+        source: globset::Error,
+    },
+    /// an I/O operation failed, `path` is the file it was made on when it is known.
+    Io {
+        path: Option<PathBuf>,
+        source: std::io::Error,
+    },
+    /// an operation was refused because it would break a rule, such as applying a
+    /// permission to a file of another type.
+    Policy {
+        path: Option<PathBuf>,
+        message: String,
+    },
+    /// a change failed and restoring the modes changed before it failed as well.
+    Rollback {
+        source: Box<FullPermissionError>,
+        failures: Vec<FullPermissionError>,
+    },
+    /// any other failure only described by its message.
+    Other { message: String },
 }
 impl FullPermissionError {
+    #[deprecated(note = "build one of the variants of `FullPermissionError` instead")]
     pub fn new(message: String) -> Self {
-        Self { message }
+        Self::Other { message }
+    }
+    /// builds the error returned when a character of a parsed input is not allowed at
+    /// the given zero-based position.
+    pub(crate) fn invalid_symbol(
+        input: &str,
+        position: usize,
+        found: Option<char>,
+        expected: &str,
+    ) -> Self {
+        Self::InvalidSymbol {
+            input: input.to_string(),
+            position,
+            found,
+            expected: expected.to_string(),
+        }
+    }
+    /// builds an I/O error made on the given path.
+    pub fn io(path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        Self::Io {
+            path: Some(path.into()),
+            source,
+        }
+    }
+    /// builds an error for an operation refused on the given path.
+    pub fn policy(path: impl Into<PathBuf>, message: impl Into<String>) -> Self {
+        Self::Policy {
+            path: Some(path.into()),
+            message: message.into(),
+        }
+    }
+    /// returns the path of the file the error happened on, if it is known.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::Io { path, .. } | Self::Policy { path, .. } => path.as_deref(),
+            _ => None,
+        }
+    }
+    /// attaches the path to the errors that are made on a file and do not have one yet.
+    pub fn with_path(mut self, new_path: impl Into<PathBuf>) -> Self {
+        if let Self::Io { path, .. } | Self::Policy { path, .. } = &mut self {
+            if path.is_none() {
+                *path = Some(new_path.into());
+            }
+        }
+        self
     }
 }
 impl Display for FullPermissionError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.message)
+        match self {
+            Self::InvalidMode { value } => write!(
+                f,
+                "the mode that was provided is not valid 6 digit decimal number that can be \
+                 parsed correctly into octal mode :{value:06o}"
+            ),
+            Self::ModeOutOfRange {
+                kind,
+                value,
+                allowed,
+            } => write!(
+                f,
+                "the {kind} `{value:o}` is out of range, only the bits of `{allowed:o}` are allowed"
+            ),
+            Self::InvalidDigit { digit } => write!(
+                f,
+                "the permission `{digit}` is not a single octal digit between 0 and 7"
+            ),
+            Self::InvalidSymbol {
+                input,
+                position,
+                found,
+                expected,
+            } => {
+                match found {
+                    Some(found) => write!(
+                        f,
+                        "invalid character `{found}` at position {position} of `{input}`, "
+                    )?,
+                    None => write!(f, "the input `{input}` ends at position {position}, ")?,
+                }
+                if expected.is_empty() {
+                    write!(f, "expected the end of the input")
+                } else {
+                    write!(f, "expected one of `{expected}`")
+                }
+            }
+            Self::InvalidNumber {
+                input,
+                radix,
+                source,
+            } => {
+                let name = if *radix == 8 { "octal" } else { "decimal" };
+                write!(f, "`{input}` is not a valid {name} mode: {source}")
+            }
+            Self::AmbiguousMode {
+                input,
+                octal,
+                decimal,
+            } => write!(
+                f,
+                "the mode `{input}` is ambiguous, it can be read as octal {octal:06o} or as \
+                 decimal {decimal} (octal {decimal:06o}), give a hint to choose one"
+            ),
+            Self::InvalidLine {
+                line,
+                input,
+                reason,
+            } => write!(f, "line {line} `{input}` is not valid: {reason}"),
+            Self::InvalidFormat {
+                format,
+                position,
+                reason,
+            } => write!(
+                f,
+                "the format `{format}` is not valid at position {position}: {reason}"
+            ),
+            Self::Rollback { source, failures } => {
+                write!(f, "{source}, and restoring the previous modes failed:")?;
+                for failure in failures {
+                    write!(f, " {failure};")?;
+                }
+                Ok(())
+            }
+            Self::UnrecognizedMode { input } => write!(
+                f,
+                "the mode `{input}` is not a valid octal, decimal or symbolic mode"
+            ),
+            Self::UnknownFileType { bits } => {
+                write!(f, "the file type bits `{bits:06o}` are not known")
+            }
+            Self::InvalidTimeStyle { input } => write!(
+                f,
+                "the time style `{input}` is not one of `full-iso`, `long-iso`, `iso`, \
+                 `locale` or `+FORMAT`"
+            ),
+            Self::InvalidGlob { pattern, source } => {
+                write!(f, "the glob pattern `{pattern}` is not valid: {source}")
+            }
+            Self::Io {
+                path: Some(path),
+                source,
+            } => write!(f, "{}: {source}", path.display()),
+            Self::Io { path: None, source } => write!(f, "{source}"),
+            Self::Policy {
+                path: Some(path),
+                message,
+            } => write!(f, "{}: {message}", path.display()),
+            Self::Policy {
+                path: None,
+                message,
+            }
+            | Self::Other { message } => write!(f, "{message}"),
+        }
+    }
+}
+impl Error for FullPermissionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidNumber { source, .. } => Some(source),
+            Self::InvalidGlob { source, .. } => Some(source),
+            Self::Io { source, .. } => Some(source),
+            Self::Rollback { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
     }
 }
-impl Error for FullPermissionError {}
 
+/// keeps the structured error when the boxed error is one of the crate or an I/O error,
+/// any other error is kept as its message.
 impl From<Box<dyn Error>> for FullPermissionError {
     fn from(value: Box<dyn Error>) -> Self {
-        FullPermissionError::new(value.to_string())
+        let value = match value.downcast::<FullPermissionError>() {
+            Ok(error) => return *error,
+            Err(value) => value,
+        };
+        match value.downcast::<std::io::Error>() {
+            Ok(error) => FullPermissionError::from(*error),
+            Err(value) => FullPermissionError::Other {
+                message: value.to_string(),
+            },
+        }
     }
 }
 impl From<std::io::Error> for FullPermissionError {
     fn from(value: std::io::Error) -> Self {
-        FullPermissionError::new(value.to_string())
+        FullPermissionError::Io {
+            path: None,
+            source: value,
+        }
     }
 }
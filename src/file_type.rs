@@ -0,0 +1,231 @@
+use crate::category_util::mode_bits::FILE_TYPE_MASK;
+use crate::FullPermissionError;
+use std::fmt::{Display, Formatter};
+use std::os::unix::fs::FileTypeExt;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+/// the kind of a file as stored in the `S_IFMT` bits of its mode.
+///
+/// the kinds that only exist on some systems are kept so that their modes can be
+/// represented, and any other type bits are reported as [`Unknown`](FileType::Unknown)
+/// instead of being mistaken for a regular file.
+///
+/// a mode without any type bits, such as `0o644`, is treated as a regular file.
+///
+/// # Example
+/// ```
+/// use fmodeparser::FileType;
+///
+/// assert_eq!(FileType::from_bits(0o040755), FileType::Directory);
+/// assert_eq!(FileType::Symlink.symbol(), 'l');
+/// assert_eq!(FileType::try_from('p').unwrap().bits(), 0o010000);
+/// assert_eq!(FileType::from_bits(0o070000), FileType::Unknown(0o070000));
+/// ```
+pub enum FileType {
+    /// `-` regular file (`S_IFREG`).
+    Regular,
+    /// `d` directory (`S_IFDIR`).
+    Directory,
+    /// `l` symbolic link (`S_IFLNK`).
+    Symlink,
+    /// `c` character device (`S_IFCHR`).
+    CharDevice,
+    /// `b` block device (`S_IFBLK`).
+    BlockDevice,
+    /// `p` named pipe (`S_IFIFO`).
+    Fifo,
+    /// `s` socket (`S_IFSOCK`).
+    Socket,
+    /// `D` Solaris door (`S_IFDOOR`).
+    Door,
+    /// `w` BSD whiteout (`S_IFWHT`).
+    Whiteout,
+    /// `P` Solaris event port (`S_IFPORT`), it shares its bits with the BSD whiteout.
+    Port,
+    /// type bits that are not known, shown as `?` like `ls` does.
+    Unknown(u32),
+}
+
+impl FileType {
+    /// returns the file type stored in the `S_IFMT` bits of a mode, the other bits are ignored.
+    pub fn from_bits(mode: u32) -> Self {
+        match mode & FILE_TYPE_MASK {
+            0o100000 | 0 => FileType::Regular,
+            0o040000 => FileType::Directory,
+            0o120000 => FileType::Symlink,
+            0o020000 => FileType::CharDevice,
+            0o060000 => FileType::BlockDevice,
+            0o010000 => FileType::Fifo,
+            0o140000 => FileType::Socket,
+            0o150000 => FileType::Door,
+            #[cfg(any(target_os = "solaris", target_os = "illumos"))]
+            0o160000 => FileType::Port,
+            #[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+            0o160000 => FileType::Whiteout,
+            bits => FileType::Unknown(bits),
+        }
+    }
+    /// returns the `S_IFMT` bits of the file type.
+    pub fn bits(&self) -> u32 {
+        match self {
+            FileType::Regular => 0o100000,
+            FileType::Directory => 0o040000,
+            FileType::Symlink => 0o120000,
+            FileType::CharDevice => 0o020000,
+            FileType::BlockDevice => 0o060000,
+            FileType::Fifo => 0o010000,
+            FileType::Socket => 0o140000,
+            FileType::Door => 0o150000,
+            FileType::Whiteout | FileType::Port => 0o160000,
+            FileType::Unknown(bits) => bits & FILE_TYPE_MASK,
+        }
+    }
+    /// returns the letter used by `ls -l` for the file type.
+    pub fn symbol(&self) -> char {
+        match self {
+            FileType::Regular => '-',
+            FileType::Directory => 'd',
+            FileType::Symlink => 'l',
+            FileType::CharDevice => 'c',
+            FileType::BlockDevice => 'b',
+            FileType::Fifo => 'p',
+            FileType::Socket => 's',
+            FileType::Door => 'D',
+            FileType::Whiteout => 'w',
+            FileType::Port => 'P',
+            FileType::Unknown(_) => '?',
+        }
+    }
+    /// returns the name `stat` gives to the file type, such as `regular file` or
+    /// `symbolic link`.
+    pub fn description(&self) -> &'static str {
+        match self {
+            FileType::Regular => "regular file",
+            FileType::Directory => "directory",
+            FileType::Symlink => "symbolic link",
+            FileType::CharDevice => "character special file",
+            FileType::BlockDevice => "block special file",
+            FileType::Fifo => "fifo",
+            FileType::Socket => "socket",
+            FileType::Door => "door",
+            FileType::Whiteout => "whiteout",
+            FileType::Port => "port",
+            FileType::Unknown(_) => "weird file",
+        }
+    }
+    /// returns the file type of an `ls -l` letter or `None` if the letter is not known.
+    pub fn from_symbol(symbol: char) -> Option<Self> {
+        let file_type = match symbol {
+            '-' => FileType::Regular,
+            'd' => FileType::Directory,
+            'l' => FileType::Symlink,
+            'c' => FileType::CharDevice,
+            'b' => FileType::BlockDevice,
+            'p' => FileType::Fifo,
+            's' => FileType::Socket,
+            'D' => FileType::Door,
+            'w' => FileType::Whiteout,
+            'P' => FileType::Port,
+            _ => return None,
+        };
+        Some(file_type)
+    }
+    /// returns the file type stored in the `S_IFMT` bits of a mode like
+    /// [`from_bits`](FileType::from_bits) but fails on type bits that are not known.
+    pub fn try_from_bits(mode: u32) -> Result<Self, FullPermissionError> {
+        match FileType::from_bits(mode) {
+            FileType::Unknown(bits) => Err(FullPermissionError::UnknownFileType { bits }),
+            file_type => Ok(file_type),
+        }
+    }
+    /// returns `true` if the type bits are not known by the crate.
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, FileType::Unknown(_))
+    }
+}
+
+impl Display for FileType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// allows comparing the file type with its `ls -l` letter like `file_type == 'd'`.
+impl PartialEq<char> for FileType {
+    fn eq(&self, other: &char) -> bool {
+        self.symbol() == *other
+    }
+}
+
+impl TryFrom<char> for FileType {
+    type Error = FullPermissionError;
+
+    fn try_from(symbol: char) -> Result<Self, Self::Error> {
+        FileType::from_symbol(symbol).ok_or_else(|| {
+            FullPermissionError::invalid_symbol(&symbol.to_string(), 0, Some(symbol), "-dlcbpsDwP")
+        })
+    }
+}
+
+/// the values that name a file type on their own: a [`FileType`] or its `ls -l` letter.
+///
+/// it is the argument of [`FullPermissionBuilder::file_type`](crate::FullPermissionBuilder::file_type),
+/// a mode number is not accepted since its permission bits would be dropped silently.
+pub trait IntoFileType {
+    /// returns the file type, or an error for a letter that is not a file type.
+    fn into_file_type(self) -> Result<FileType, FullPermissionError>;
+}
+
+impl IntoFileType for FileType {
+    fn into_file_type(self) -> Result<FileType, FullPermissionError> {
+        Ok(self)
+    }
+}
+
+impl IntoFileType for char {
+    fn into_file_type(self) -> Result<FileType, FullPermissionError> {
+        FileType::try_from(self)
+    }
+}
+
+impl From<u32> for FileType {
+    fn from(mode: u32) -> Self {
+        FileType::from_bits(mode)
+    }
+}
+
+impl From<FileType> for char {
+    fn from(file_type: FileType) -> Self {
+        file_type.symbol()
+    }
+}
+
+impl From<FileType> for u32 {
+    fn from(file_type: FileType) -> Self {
+        file_type.bits()
+    }
+}
+
+/// converts the standard library file type, the kinds it cannot tell apart are reported
+/// as [`Unknown`](FileType::Unknown) without bits.
+impl From<std::fs::FileType> for FileType {
+    fn from(file_type: std::fs::FileType) -> Self {
+        if file_type.is_file() {
+            FileType::Regular
+        } else if file_type.is_dir() {
+            FileType::Directory
+        } else if file_type.is_symlink() {
+            FileType::Symlink
+        } else if file_type.is_char_device() {
+            FileType::CharDevice
+        } else if file_type.is_block_device() {
+            FileType::BlockDevice
+        } else if file_type.is_fifo() {
+            FileType::Fifo
+        } else if file_type.is_socket() {
+            FileType::Socket
+        } else {
+            FileType::Unknown(0)
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! This module contains the `find -perm` test: the exact, all-of and any-of comparisons of
+//! a mode with an octal or symbolic operand, and a walker that yields the matching paths
+//! under a root.
+//!
+
+use crate::{FileType, FullPermission, FullPermissionError, PermStrParser, SymbolicMode};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// the permission and special bits `find -perm` compares.
+const PERM_BITS: u32 = 0o7777;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+/// how the mode of a file is compared with the operand of `-perm`.
+pub enum PermMatch {
+    /// `-perm mode`: the permission and special bits are exactly the operand.
+    #[default]
+    Exact,
+    /// `-perm -mode`: every bit of the operand is set.
+    AllOf,
+    /// `-perm /mode`: at least one bit of the operand is set, an operand without any bit
+    /// matches every file.
+    AnyOf,
+}
+
+impl PermMatch {
+    /// returns the prefix of the operand: nothing, `-` or `/`.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            PermMatch::Exact => "",
+            PermMatch::AllOf => "-",
+            PermMatch::AnyOf => "/",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+/// a `find -perm` test.
+///
+/// the operand is octal (`644`, `-4000`) or symbolic (`-u+w,g+w`, `/o+w`, `g=s`), a
+/// symbolic operand is applied to an empty mode without umask. like `find` a symbolic
+/// operand is computed twice, for the directories and for the other files, so `X` only
+/// asks for the execute bits of directories.
+///
+/// the deprecated `-perm +mode` of old `find` versions is read like the current GNU `find`
+/// does, as the exact symbolic operand `+mode`.
+///
+/// # Example
+/// ```
+/// use fmodeparser::{FindPerm, FullPermission, PermMatch};
+/// # use fmodeparser::FullPermissionError;
+/// # fn main() -> Result<(), FullPermissionError> {
+///
+/// let world_writable: FindPerm = "/o+w".parse()?;
+/// assert_eq!(world_writable.get_kind(), PermMatch::AnyOf);
+/// assert!(world_writable.matches(FullPermission::new(0o100666)?));
+/// assert!(!world_writable.matches(FullPermission::new(0o100644)?));
+///
+/// let setuid: FindPerm = "-4000".parse()?;
+/// assert!(setuid.matches(FullPermission::new(0o104755)?));
+/// assert!("-u+w,g+w".parse::<FindPerm>()?.matches("-rw-rw-r--".parse()?));
+/// assert!("644".parse::<FindPerm>()?.matches_mode(0o100644));
+/// # Ok(())
+/// # }
+/// ```
+pub struct FindPerm {
+    kind: PermMatch,
+    operand: String,
+    file_bits: u32,
+    directory_bits: u32,
+}
+
+impl FindPerm {
+    /// returns the test of the octal bits with the given comparison.
+    pub fn new(kind: PermMatch, bits: u32) -> Result<Self, FullPermissionError> {
+        if bits & !PERM_BITS != 0 {
+            return Err(FullPermissionError::ModeOutOfRange {
+                kind: "find mode",
+                value: bits,
+                allowed: PERM_BITS,
+            });
+        }
+        Ok(Self {
+            kind,
+            operand: format!("{bits:o}"),
+            file_bits: bits,
+            directory_bits: bits,
+        })
+    }
+    /// returns the test of an operand without its prefix, octal or symbolic.
+    pub fn with_operand(kind: PermMatch, operand: &str) -> Result<Self, FullPermissionError> {
+        if !operand.is_empty() && operand.bytes().all(|byte| byte.is_ascii_digit()) {
+            let bits = u32::from_str_radix(operand, 8).map_err(|source| {
+                FullPermissionError::InvalidNumber {
+                    input: operand.to_string(),
+                    radix: 8,
+                    source,
+                }
+            })?;
+            return Ok(Self {
+                operand: operand.to_string(),
+                ..Self::new(kind, bits)?
+            });
+        }
+        let expression: SymbolicMode = operand.parse()?;
+        Ok(Self {
+            kind,
+            operand: operand.to_string(),
+            file_bits: expression.apply_to_mode(FileType::Regular.bits(), 0) & PERM_BITS,
+            directory_bits: expression.apply_to_mode(FileType::Directory.bits(), 0) & PERM_BITS,
+        })
+    }
+    /// returns the comparison of the test.
+    pub fn get_kind(&self) -> PermMatch {
+        self.kind
+    }
+    /// returns the bits compared with the mode of a file of the given type.
+    pub fn get_bits(&self, file_type: FileType) -> u32 {
+        if file_type == FileType::Directory {
+            self.directory_bits
+        } else {
+            self.file_bits
+        }
+    }
+    /// returns `true` if the permission passes the test.
+    pub fn matches(&self, permission: FullPermission) -> bool {
+        self.matches_mode(permission.get_mode())
+    }
+    /// returns `true` if the raw mode passes the test, its file type bits choose the
+    /// operand of the directories or of the other files.
+    pub fn matches_mode(&self, mode: u32) -> bool {
+        let bits = self.get_bits(FileType::from_bits(mode));
+        let mode = mode & PERM_BITS;
+        match self.kind {
+            PermMatch::Exact => mode == bits,
+            PermMatch::AllOf => mode & bits == bits,
+            PermMatch::AnyOf => bits == 0 || mode & bits != 0,
+        }
+    }
+    /// walks the tree under `root` like `find root -perm ...` and yields the matching
+    /// paths, the root included.
+    ///
+    /// symbolic links are tested themselves and never followed. the entries of a
+    /// directory are visited in name order, and a directory that cannot be read yields an
+    /// error before the walk goes on with the next entry.
+    pub fn find(&self, root: impl Into<PathBuf>) -> FindPermIter {
+        FindPermIter {
+            test: self.clone(),
+            pending: vec![Visit::Path(root.into())],
+        }
+    }
+}
+
+/// parses a `-perm` operand with its optional `-` or `/` prefix.
+impl FromStr for FindPerm {
+    type Err = FullPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.as_bytes().first() {
+            Some(b'-') => FindPerm::with_operand(PermMatch::AllOf, &s[1..]),
+            Some(b'/') => FindPerm::with_operand(PermMatch::AnyOf, &s[1..]),
+            _ => FindPerm::with_operand(PermMatch::Exact, s),
+        }
+    }
+}
+
+impl TryFrom<&str> for FindPerm {
+    type Error = FullPermissionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// writes the operand of `-perm` with its prefix.
+impl Display for FindPerm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.kind.prefix(), self.operand)
+    }
+}
+
+#[derive(Debug)]
+/// a step of the walk.
+enum Visit {
+    /// tests a path.
+    Path(PathBuf),
+    /// reads the entries of a directory that was tested.
+    Children(PathBuf),
+}
+
+#[derive(Debug)]
+/// the iterator of the paths that pass a [`FindPerm`] test, returned by
+/// [`FindPerm::find`].
+pub struct FindPermIter {
+    test: FindPerm,
+    /// the steps left, the next one last.
+    pending: Vec<Visit>,
+}
+
+impl FindPermIter {
+    /// queues the entries of a directory so they are visited in name order.
+    fn push_children(&mut self, directory: &Path) -> Result<(), FullPermissionError> {
+        let mut children = std::fs::read_dir(directory)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map_err(|error| FullPermissionError::io(directory, error))?;
+        children.sort();
+        self.pending
+            .extend(children.into_iter().rev().map(Visit::Path));
+        Ok(())
+    }
+}
+
+impl Iterator for FindPermIter {
+    type Item = Result<PathBuf, FullPermissionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(visit) = self.pending.pop() {
+            let path = match visit {
+                Visit::Path(path) => path,
+                Visit::Children(directory) => match self.push_children(&directory) {
+                    Ok(()) => continue,
+                    Err(error) => return Some(Err(error)),
+                },
+            };
+            let permission = match path.get_full_permission() {
+                Ok(permission) => permission,
+                Err(error) => return Some(Err(error)),
+            };
+            // a directory is tested before its entries, like `find` does
+            if permission.get_file_type() == FileType::Directory {
+                self.pending.push(Visit::Children(path.clone()));
+            }
+            if self.test.matches(permission) {
+                return Some(Ok(path));
+            }
+        }
+        None
+    }
+}
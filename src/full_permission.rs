@@ -1,18 +1,28 @@
+use crate::category_util::mode_bits::*;
 use crate::category_util::owners::{OwnersKind, OwnersKind::*};
-use crate::category_util::permssion_conver_util::*;
+use crate::category_util::permssion_conver_util::execute_with_special_symbol;
 use crate::FullPermissionError;
 use crate::ModeParser;
-use regex::Regex;
-use std::cell::RefCell;
+use crate::SymbolicMode;
+use crate::{Class, Perm, PermSet, WhoSet};
+use crate::{FileType, IntoFileType};
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
-#[derive(Debug, Eq, PartialEq)]
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 /// a struct that represents a file permission for the file kind and the three owner kinds
 ///
 /// allows easily access, modify and convert the permission to a string representation
 ///
+/// it is a plain `Copy` value that is `Send + Sync`, reads take `&self` and changes are
+/// made either through the `&mut self` setters or the consuming `with_*` methods.
+/// permissions are ordered by their [`get_mode`](FullPermission::get_mode) number.
+///
 /// see the example for more details. [new](FullPermission::new)
 pub struct FullPermission {
-    file_type: char,
+    file_type: FileType,
+    special: ModeBits,
     user: OwnersKind,
     group: OwnersKind,
     other: OwnersKind,
@@ -23,83 +33,394 @@ impl FullPermission {
     ///
     /// returns either `instance` of the struct or an `FullPermissionError` Result.
     ///
+    /// the special bits (setuid, setgid and sticky) are decoded as well and shown the
+    /// same way `ls -l` does.
+    ///
     /// # Example
     ///```
     /// use fmodeparser::FullPermission;
+    /// # use fmodeparser::FullPermissionError;
+    /// # fn main() -> Result<(), FullPermissionError> {
     ///
     /// let mode_number = 33188;
     /// let full_permission = FullPermission::new(mode_number)?;
+    /// assert_eq!(full_permission.to_string(), "-rw-r--r--");
+    ///
+    /// let setuid_binary = FullPermission::new(0o104755)?;
+    /// assert_eq!(setuid_binary.to_string(), "-rwsr-xr-x");
+    /// # Ok(())
+    /// # }
     /// ```
     ///
     pub fn new(mode: u32) -> Result<Self, FullPermissionError> {
         // accepts a decimal number represents a file mode and returns a string of the permission
         // it will automatically convert it to octal and then convert it to a string representation
-        let mode_oc = format!("{:06o}", mode);
-        if mode_oc.len() != 6 {
-            return Err(FullPermissionError::new(format!("the mode that was provided is \
-            not valid 6 digit decimal number that can be parsed correctly into octal mode :{mode_oc}")));
-        }
-        let file_type = file_type_number_to_symbol(&mode_oc[0..=2]);
-        let user = digit_to_permission(&mode_oc[3..=3]); // `---` as an example
-        let group = digit_to_permission(&mode_oc[4..=4]);
-        let other = digit_to_permission(&mode_oc[5..=5]);
+        if mode >= MODE_LIMIT {
+            return Err(FullPermissionError::InvalidMode { value: mode });
+        }
         Ok(Self {
-            file_type,
-            user: User(RefCell::new(ModeParser::from(user))),
-            group: Group(RefCell::new(ModeParser::from(group))),
-            other: Other(RefCell::new(ModeParser::from(other))),
+            file_type: FileType::from_bits(mode),
+            special: ModeBits::from_bits_truncate(mode) & ModeBits::SPECIAL,
+            user: User(ModeParser::from(mode >> 6)),
+            group: Group(ModeParser::from(mode >> 3)),
+            other: Other(ModeParser::from(mode)),
         })
     }
-    /// returns character representing the file type. example: `-` for a regular file
-    pub fn get_file_type(&self) -> char {
+    /// returns the file type. example: [`FileType::Regular`] shown as `-` for a regular file
+    ///
+    /// the file type can be compared directly with its `ls` letter: `get_file_type() == 'd'`.
+    pub fn get_file_type(&self) -> FileType {
         self.file_type
     }
+    /// assigns a new file type, the permission bits are kept.
+    pub fn set_file_type(&mut self, file_type: FileType) {
+        self.file_type = file_type;
+    }
+    /// returns a copy with the file type replaced.
+    pub fn with_file_type(mut self, file_type: FileType) -> Self {
+        self.set_file_type(file_type);
+        self
+    }
+    /// returns `true` if the set-user-ID bit is set.
+    pub fn get_setuid(&self) -> bool {
+        self.special.contains(ModeBits::SETUID)
+    }
+    /// returns `true` if the set-group-ID bit is set.
+    pub fn get_setgid(&self) -> bool {
+        self.special.contains(ModeBits::SETGID)
+    }
+    /// returns `true` if the sticky (restricted deletion) bit is set.
+    pub fn get_sticky(&self) -> bool {
+        self.special.contains(ModeBits::STICKY)
+    }
+    /// assigns a new set-user-ID bit, shown as `s`/`S` in the user execute slot.
+    pub fn set_setuid(&mut self, setuid: bool) {
+        self.special.set(ModeBits::SETUID, setuid);
+    }
+    /// assigns a new set-group-ID bit, shown as `s`/`S` in the group execute slot.
+    pub fn set_setgid(&mut self, setgid: bool) {
+        self.special.set(ModeBits::SETGID, setgid);
+    }
+    /// assigns a new sticky bit, shown as `t`/`T` in the other execute slot.
+    pub fn set_sticky(&mut self, sticky: bool) {
+        self.special.set(ModeBits::STICKY, sticky);
+    }
+    /// returns the special bits as a single octal digit (`4` setuid, `2` setgid, `1` sticky).
+    pub fn get_special_mode(&self) -> u32 {
+        self.special.bits() >> 9
+    }
+    /// returns the permission and special bits without the file type bits.
+    pub fn get_mode_bits(&self) -> ModeBits {
+        let user = self.user.get_partial_mode() << 6;
+        let group = self.group.get_partial_mode() << 3;
+        let other = self.other.get_partial_mode();
+        self.special | ModeBits::from_bits_truncate(user | group | other)
+    }
+    /// assigns the permission and special bits, the file type is kept.
+    pub fn set_mode_bits(&mut self, bits: ModeBits) {
+        let bits = bits.bits();
+        self.special = ModeBits::from_bits_truncate(bits) & ModeBits::SPECIAL;
+        self.user.set_partial_mode(bits >> 6);
+        self.group.set_partial_mode(bits >> 3);
+        self.other.set_partial_mode(bits);
+    }
+    /// returns a copy with the permission and special bits replaced, the file type is kept.
+    pub fn with_mode_bits(mut self, bits: ModeBits) -> Self {
+        self.set_mode_bits(bits);
+        self
+    }
+    /// returns the user owner kind.
+    pub fn user(&self) -> &OwnersKind {
+        &self.user
+    }
+    /// returns the group owner kind.
+    pub fn group(&self) -> &OwnersKind {
+        &self.group
+    }
+    /// returns the other owner kind.
+    pub fn other(&self) -> &OwnersKind {
+        &self.other
+    }
     /// returns the user owner that gives you the full authority to modify this owner kind.
-    pub fn get_user(&mut self) -> &mut OwnersKind {
+    pub fn user_mut(&mut self) -> &mut OwnersKind {
         &mut self.user
     }
-
     /// returns the group owner that gives you the full authority to modify this owner kind.
-    pub fn get_group(&mut self) -> &mut OwnersKind {
+    pub fn group_mut(&mut self) -> &mut OwnersKind {
         &mut self.group
     }
     /// returns the other owner that gives you the full authority to modify this owner kind.
-    pub fn get_other(&mut self) -> &mut OwnersKind {
+    pub fn other_mut(&mut self) -> &mut OwnersKind {
         &mut self.other
     }
+    /// returns the user owner that gives you the full authority to modify this owner kind.
+    #[deprecated(note = "use `user` to read or `user_mut` to modify the user owner kind")]
+    pub fn get_user(&mut self) -> &mut OwnersKind {
+        self.user_mut()
+    }
+    /// returns the group owner that gives you the full authority to modify this owner kind.
+    #[deprecated(note = "use `group` to read or `group_mut` to modify the group owner kind")]
+    pub fn get_group(&mut self) -> &mut OwnersKind {
+        self.group_mut()
+    }
+    /// returns the other owner that gives you the full authority to modify this owner kind.
+    #[deprecated(note = "use `other` to read or `other_mut` to modify the other owner kind")]
+    pub fn get_other(&mut self) -> &mut OwnersKind {
+        self.other_mut()
+    }
+    /// returns a copy with the user permission replaced by a single octal digit like `7`.
+    pub fn with_user(mut self, partial_mode: u32) -> Self {
+        self.user.set_partial_mode(partial_mode);
+        self
+    }
+    /// returns a copy with the group permission replaced by a single octal digit like `5`.
+    pub fn with_group(mut self, partial_mode: u32) -> Self {
+        self.group.set_partial_mode(partial_mode);
+        self
+    }
+    /// returns a copy with the other permission replaced by a single octal digit like `4`.
+    pub fn with_other(mut self, partial_mode: u32) -> Self {
+        self.other.set_partial_mode(partial_mode);
+        self
+    }
+    /// returns a copy with the set-user-ID bit replaced.
+    pub fn with_setuid(mut self, setuid: bool) -> Self {
+        self.set_setuid(setuid);
+        self
+    }
+    /// returns a copy with the set-group-ID bit replaced.
+    pub fn with_setgid(mut self, setgid: bool) -> Self {
+        self.set_setgid(setgid);
+        self
+    }
+    /// returns a copy with the sticky bit replaced.
+    pub fn with_sticky(mut self, sticky: bool) -> Self {
+        self.set_sticky(sticky);
+        self
+    }
+    /// adds the permissions to every class of `who`, like `chmod ug+w`.
+    ///
+    /// # Example
+    /// ```
+    /// use fmodeparser::{Class, FullPermission, Perm, PermSet, WhoSet};
+    /// # use fmodeparser::FullPermissionError;
+    /// # fn main() -> Result<(), FullPermissionError> {
+    ///
+    /// let mut permission = FullPermission::new(0o100664)?;
+    /// permission.revoke(WhoSet::GROUP | WhoSet::OTHER, PermSet::WRITE);
+    /// permission.grant(WhoSet::USER, Perm::Execute);
+    /// assert_eq!(permission.to_string(), "-rwxr--r--");
+    /// assert!(permission.has(Class::User, Perm::Execute));
+    /// assert_eq!(permission[Class::Group].to_string(), "r--");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn grant(&mut self, who: impl Into<WhoSet>, perms: impl Into<PermSet>) {
+        let perms = perms.into().bits();
+        for class in who.into().classes() {
+            let owner = &mut self[class];
+            owner.set_partial_mode(owner.get_partial_mode() | perms);
+        }
+    }
+    /// removes the permissions from every class of `who`, like `chmod go-w`.
+    pub fn revoke(&mut self, who: impl Into<WhoSet>, perms: impl Into<PermSet>) {
+        let perms = perms.into().bits();
+        for class in who.into().classes() {
+            let owner = &mut self[class];
+            owner.set_partial_mode(owner.get_partial_mode() & !perms);
+        }
+    }
+    /// replaces the permissions of every class of `who` with exactly `perms`, like `chmod u=rw`.
+    pub fn assign(&mut self, who: impl Into<WhoSet>, perms: impl Into<PermSet>) {
+        let perms = perms.into().bits();
+        for class in who.into().classes() {
+            self[class].set_partial_mode(perms);
+        }
+    }
+    /// returns `true` if the class has the permission.
+    pub fn has(&self, class: Class, perm: Perm) -> bool {
+        self[class].get_partial_mode() & perm.bit() != 0
+    }
     /// returns the mode of the file converted from octal to decimal.
     ///
     /// Also it can be reused again to construct a new [`FullPermission`](FullPermission) object.
-    pub fn get_mode(&mut self) -> u32 {
-        // get the mode of the file as an octal number then converts it to a decimal number
-        // so that ,it can re passed again to a new FullPermission object
-        let num = symbol_to_file_type_number(self.get_file_type());
-        let user = self.user.get_partial_mode(); // as an octal number
-        let group = self.group.get_partial_mode(); // as an octal number
-        let other = self.other.get_partial_mode(); // as an octal number
-
-        u32::from_str_radix(&format!("{num}{user}{group}{other}"), 8).unwrap_or(0)
+    pub fn get_mode(&self) -> u32 {
+        // the type bits and the permission bits never overlap so they are simply combined
+        self.file_type.bits() | self.get_mode_bits().bits()
+    }
+    /// parses and applies a `chmod` symbolic mode expression such as `u+x,g-w,o=r`.
+    ///
+    /// `umask` is only used by the clauses without a `who` part like `+x`, see
+    /// [`SymbolicMode`](crate::SymbolicMode) for the full syntax.
+    pub fn apply_symbolic(
+        &mut self,
+        expression: &str,
+        umask: u32,
+    ) -> Result<(), FullPermissionError> {
+        expression.parse::<SymbolicMode>()?.apply(self, umask)
     }
     /// returns the mode as an octal number string.
     ///
     /// this is not meant to be used to be bypassed within the crate !! only for representation.
-    pub fn mode_as_octal(&mut self) -> String {
+    pub fn mode_as_octal(&self) -> String {
         // this is not meant to be used to be bypassed within the crate !! only for representation
         format!("{:06o}", self.get_mode())
     }
 }
+impl Index<Class> for FullPermission {
+    type Output = OwnersKind;
+
+    fn index(&self, class: Class) -> &Self::Output {
+        match class {
+            Class::User => &self.user,
+            Class::Group => &self.group,
+            Class::Other => &self.other,
+        }
+    }
+}
+impl IndexMut<Class> for FullPermission {
+    fn index_mut(&mut self, class: Class) -> &mut Self::Output {
+        match class {
+            Class::User => &mut self.user,
+            Class::Group => &mut self.group,
+            Class::Other => &mut self.other,
+        }
+    }
+}
+impl PartialOrd for FullPermission {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for FullPermission {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.get_mode().cmp(&other.get_mode())
+    }
+}
 impl Display for FullPermission {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}{}{}{}",
-            self.file_type, self.user, self.group, self.other
-        )
+        write!(f, "{}", self.get_file_type())?;
+        for (owner, special, symbol) in [
+            (&self.user, self.get_setuid(), 's'),
+            (&self.group, self.get_setgid(), 's'),
+            (&self.other, self.get_sticky(), 't'),
+        ] {
+            let execute = execute_with_special_symbol(owner.get_execute(), special, symbol);
+            write!(f, "{}{}{}", owner.get_read(), owner.get_write(), execute)?;
+        }
+        Ok(())
+    }
+}
+
+/// parses the 10-character `ls -l` form of a permission such as `-rw-r--r--` or `drwxrwxrwt`.
+///
+/// the special bits letters `s`, `S`, `t` and `T` are accepted in the execute slots and a
+/// single trailing ACL/extended attribute marker (`+`, `.` or `@`) is accepted and dropped.
+///
+/// the error message points at the zero-based position of the first offending character.
+///
+/// # Example
+/// ```
+/// use fmodeparser::FullPermission;
+/// # use fmodeparser::FullPermissionError;
+/// # fn main() -> Result<(), FullPermissionError> {
+///
+/// let permission: FullPermission = "-rwsr-xr-x".parse()?;
+/// assert_eq!(permission.mode_as_octal(), "104755");
+///
+/// let permission = FullPermission::try_from("drwxr-xr-x+")?;
+/// assert_eq!(permission.get_mode(), 0o40755);
+/// # Ok(())
+/// # }
+/// ```
+impl FromStr for FullPermission {
+    type Err = FullPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+        let Some(file_type) = chars.first().copied().and_then(FileType::from_symbol) else {
+            return Err(FullPermissionError::invalid_symbol(
+                s,
+                0,
+                chars.first().copied(),
+                "-dlcbpsDwP",
+            ));
+        };
+        let mut mode = file_type.bits();
+        for (index, (shift, special_bit, special_symbol)) in
+            [(6, 0o4000, "s"), (3, 0o2000, "s"), (0, 0o1000, "t")]
+                .into_iter()
+                .enumerate()
+        {
+            let offset = 1 + index * 3;
+            let (digit, special) = parse_triplet(s, &chars, offset, special_symbol)?;
+            mode |= digit << shift;
+            if special {
+                mode |= special_bit;
+            }
+        }
+        // an optional ACL (`+`), SELinux (`.`) or extended attributes (`@`) marker may follow
+        let end = match chars.get(10) {
+            Some('+' | '.' | '@') => 11,
+            Some(&found) => {
+                return Err(FullPermissionError::invalid_symbol(
+                    s,
+                    10,
+                    Some(found),
+                    "+.@",
+                ))
+            }
+            None => 10,
+        };
+        if let Some(&found) = chars.get(end) {
+            return Err(FullPermissionError::invalid_symbol(s, end, Some(found), ""));
+        }
+        FullPermission::new(mode)
+    }
+}
+
+impl TryFrom<&str> for FullPermission {
+    type Error = FullPermissionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<u32> for FullPermission {
+    type Error = FullPermissionError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        FullPermission::new(value)
+    }
+}
+
+/// returns the permission of a regular file with the given permission and special bits.
+impl From<ModeBits> for FullPermission {
+    fn from(bits: ModeBits) -> Self {
+        let mode = bits.bits();
+        Self {
+            file_type: FileType::Regular,
+            special: bits & ModeBits::SPECIAL,
+            user: User(ModeParser::from(mode >> 6)),
+            group: Group(ModeParser::from(mode >> 3)),
+            other: Other(ModeParser::from(mode)),
+        }
     }
 }
 
 /// allows to construct a `FullPermission` object from a string representation of the permission.
 ///
+/// every part is stored in its own slot so the methods can be called in any order, the
+/// missing parts default to a regular file without any permission. a special letter such
+/// as `s` in a class sets its special bit, the other letters leave the bit to
+/// [`setuid`](FullPermissionBuilder::setuid), [`setgid`](FullPermissionBuilder::setgid) and
+/// [`sticky`](FullPermissionBuilder::sticky).
+///
+/// the plain setters such as [`user`](FullPermissionBuilder::user) are the panicking
+/// compatibility path kept for the existing callers, the `try_*` methods return a
+/// [`FullPermissionError`] instead of panicking on invalid input.
+///
 /// # Example
 ///
 /// ```
@@ -107,8 +428,8 @@ impl Display for FullPermission {
 /// use fmodeparser::FullPermissionError;
 /// use std::error::Error;
 ///
-///
-/// let mut permission = FullPermissionBuilder::new()
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let permission = FullPermissionBuilder::new()
 ///                          .file_type('-')
 ///                          .user("rw-")
 ///                          .group("r--")
@@ -118,71 +439,222 @@ impl Display for FullPermission {
 ///  assert_eq!(permission.to_string(), "-rw-r--r--");
 ///  assert_eq!(permission.get_mode(), 33188);
 ///  assert_eq!(permission.mode_as_octal(), "100644");
+///
+///  let permission = FullPermissionBuilder::new()
+///                          .other_octal(5)
+///                          .try_user("rws")?
+///                          .try_file_type('d')?
+///                          .build()?;
+///  assert_eq!(permission.to_string(), "drws---r-x");
 ///  Ok(())
+/// # }
 /// ```
-
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct FullPermissionBuilder {
-    mode: String,
+    file_type: FileType,
+    special: ModeBits,
+    user: u32,
+    group: u32,
+    other: u32,
 }
 impl FullPermissionBuilder {
     pub fn new() -> Self {
         Self {
-            mode: "".to_string(),
+            file_type: FileType::Regular,
+            special: ModeBits::empty(),
+            user: 0,
+            group: 0,
+            other: 0,
+        }
+    }
+    /// returns the slot of the class permission digit.
+    fn class_slot(&mut self, class: Class) -> &mut u32 {
+        match class {
+            Class::User => &mut self.user,
+            Class::Group => &mut self.group,
+            Class::Other => &mut self.other,
+        }
+    }
+    /// returns the special bit shown in the execute slot of the class and its letter.
+    fn class_special(class: Class) -> (ModeBits, &'static str) {
+        match class {
+            Class::User => (ModeBits::SETUID, "s"),
+            Class::Group => (ModeBits::SETGID, "s"),
+            Class::Other => (ModeBits::STICKY, "t"),
         }
     }
     /// helper method for setting a user,group,other permissions.
-    fn common_set_perm(&self, kind: &str, perm: &str) {
-        let reg = Regex::new("^[r-][w-][x-]$").unwrap();
-        if !reg.is_match(perm) {
-            panic!("{kind} permission must be a valid type only one of the options: rwx-")
+    fn common_set_perm(
+        &mut self,
+        class: Class,
+        perm: &str,
+    ) -> Result<&mut Self, FullPermissionError> {
+        let (special_bit, special_symbol) = Self::class_special(class);
+        let triplet: Vec<char> = perm.chars().collect();
+        let (digit, special) = parse_triplet(perm, &triplet, 0, special_symbol)?;
+        if let Some(&extra) = triplet.get(3) {
+            return Err(FullPermissionError::invalid_symbol(
+                perm,
+                3,
+                Some(extra),
+                "",
+            ));
+        }
+        *self.class_slot(class) = digit;
+        // only a special letter sets the bit, so `setuid` and friends can be called before
+        if special {
+            self.special.insert(special_bit);
+        }
+        Ok(self)
+    }
+    /// helper method for setting a user,group,other permissions from an octal digit.
+    fn common_set_octal(
+        &mut self,
+        class: Class,
+        digit: u32,
+    ) -> Result<&mut Self, FullPermissionError> {
+        if digit > 7 {
+            return Err(FullPermissionError::InvalidDigit { digit });
         }
+        *self.class_slot(class) = digit;
+        Ok(self)
     }
-    /// accepts only a single character: `(-, d,l,c,b,p,s)`.
+    /// accepts either a [`FileType`] or its single character: `(-, d,l,c,b,p,s,D,w,P)`.
     ///
     /// # Panics
-    /// if only provided an invalid character!
-    pub fn file_type(&mut self, file_type: char) -> &mut Self {
-        let reg = Regex::new("^[-dlcbps]$").unwrap();
-        if !reg.is_match(&file_type.to_string()) {
-            panic!("file type must be a valid type only one of the options: -dlcbps")
-        }
-        self.mode.push_str(&symbol_to_file_type_number(file_type));
+    /// on an invalid character, this is the panicking compatibility path of
+    /// [`try_file_type`](FullPermissionBuilder::try_file_type).
+    #[track_caller]
+    pub fn file_type(&mut self, file_type: impl IntoFileType) -> &mut Self {
+        let Ok(file_type) = file_type.into_file_type() else {
+            panic!("file type must be a valid type only one of the options: -dlcbpsDwP")
+        };
+        self.file_type = file_type;
         self
     }
-    /// accepts 3-character string representing the permission. examples : `(---, rwx, r--)`
+    /// same as [`file_type`](FullPermissionBuilder::file_type) but returns an error for an
+    /// invalid character.
+    pub fn try_file_type(
+        &mut self,
+        file_type: impl IntoFileType,
+    ) -> Result<&mut Self, FullPermissionError> {
+        self.file_type = file_type.into_file_type()?;
+        Ok(self)
+    }
+    /// accepts 3-character string representing the permission. examples : `(---, rwx, r--, rws)`
     ///
     /// # Panics
-    /// the permission must follow the regular expression `^[r-][w-][x-]$`
+    /// when the permission is not made of `[r-][w-][xsS-]`, this is the panicking
+    /// compatibility path of [`try_user`](FullPermissionBuilder::try_user).
+    #[track_caller]
     pub fn user(&mut self, user: &str) -> &mut Self {
-        self.common_set_perm("user", user);
-        self.mode.push_str(&permission_to_digit(user));
-        self
+        match self.try_user(user) {
+            Ok(builder) => builder,
+            Err(error) => panic!("user {error}"),
+        }
     }
-    /// accepts 3-character string representing the permission. examples : `(---, rwx, r--)`
+    /// accepts 3-character string representing the permission. examples : `(---, rwx, r-s)`
     ///
     /// # Panics
-    /// the permission must follow the regular expression `^[r-][w-][x-]$`
+    /// when the permission is not made of `[r-][w-][xsS-]`, this is the panicking
+    /// compatibility path of [`try_group`](FullPermissionBuilder::try_group).
+    #[track_caller]
     pub fn group(&mut self, group: &str) -> &mut Self {
-        self.common_set_perm("group", group);
-        self.mode.push_str(&permission_to_digit(group));
-
-        self
+        match self.try_group(group) {
+            Ok(builder) => builder,
+            Err(error) => panic!("group {error}"),
+        }
     }
-    /// accepts 3-character string representing the permission. examples : `(---, rwx, r--)`
+    /// accepts 3-character string representing the permission. examples : `(---, rwx, r-t)`
     ///
     /// # Panics
-    /// the permission must follow the regular expression `^[r-][w-][x-]$`
+    /// when the permission is not made of `[r-][w-][xtT-]`, this is the panicking
+    /// compatibility path of [`try_other`](FullPermissionBuilder::try_other).
+    #[track_caller]
     pub fn other(&mut self, other: &str) -> &mut Self {
-        self.common_set_perm("other", other);
-        self.mode.push_str(&permission_to_digit(other));
-
+        match self.try_other(other) {
+            Ok(builder) => builder,
+            Err(error) => panic!("other {error}"),
+        }
+    }
+    /// same as [`user`](FullPermissionBuilder::user) but returns an error for invalid input.
+    pub fn try_user(&mut self, user: &str) -> Result<&mut Self, FullPermissionError> {
+        self.common_set_perm(Class::User, user)
+    }
+    /// same as [`group`](FullPermissionBuilder::group) but returns an error for invalid input.
+    pub fn try_group(&mut self, group: &str) -> Result<&mut Self, FullPermissionError> {
+        self.common_set_perm(Class::Group, group)
+    }
+    /// same as [`other`](FullPermissionBuilder::other) but returns an error for invalid input.
+    pub fn try_other(&mut self, other: &str) -> Result<&mut Self, FullPermissionError> {
+        self.common_set_perm(Class::Other, other)
+    }
+    /// accepts a single octal digit representing the permission. examples : `(0, 7, 4)`
+    ///
+    /// # Panics
+    /// when the digit is not between 0 and 7, this is the panicking compatibility path of
+    /// [`try_user_octal`](FullPermissionBuilder::try_user_octal).
+    #[track_caller]
+    pub fn user_octal(&mut self, user: u32) -> &mut Self {
+        match self.try_user_octal(user) {
+            Ok(builder) => builder,
+            Err(error) => panic!("{error}"),
+        }
+    }
+    /// accepts a single octal digit representing the permission. examples : `(0, 7, 4)`
+    ///
+    /// # Panics
+    /// when the digit is not between 0 and 7, this is the panicking compatibility path of
+    /// [`try_group_octal`](FullPermissionBuilder::try_group_octal).
+    #[track_caller]
+    pub fn group_octal(&mut self, group: u32) -> &mut Self {
+        match self.try_group_octal(group) {
+            Ok(builder) => builder,
+            Err(error) => panic!("{error}"),
+        }
+    }
+    /// accepts a single octal digit representing the permission. examples : `(0, 7, 4)`
+    ///
+    /// # Panics
+    /// when the digit is not between 0 and 7, this is the panicking compatibility path of
+    /// [`try_other_octal`](FullPermissionBuilder::try_other_octal).
+    #[track_caller]
+    pub fn other_octal(&mut self, other: u32) -> &mut Self {
+        match self.try_other_octal(other) {
+            Ok(builder) => builder,
+            Err(error) => panic!("{error}"),
+        }
+    }
+    /// same as [`user_octal`](FullPermissionBuilder::user_octal) but returns an error.
+    pub fn try_user_octal(&mut self, user: u32) -> Result<&mut Self, FullPermissionError> {
+        self.common_set_octal(Class::User, user)
+    }
+    /// same as [`group_octal`](FullPermissionBuilder::group_octal) but returns an error.
+    pub fn try_group_octal(&mut self, group: u32) -> Result<&mut Self, FullPermissionError> {
+        self.common_set_octal(Class::Group, group)
+    }
+    /// same as [`other_octal`](FullPermissionBuilder::other_octal) but returns an error.
+    pub fn try_other_octal(&mut self, other: u32) -> Result<&mut Self, FullPermissionError> {
+        self.common_set_octal(Class::Other, other)
+    }
+    /// sets or clears the set-user-ID bit.
+    pub fn setuid(&mut self, setuid: bool) -> &mut Self {
+        self.special.set(ModeBits::SETUID, setuid);
+        self
+    }
+    /// sets or clears the set-group-ID bit.
+    pub fn setgid(&mut self, setgid: bool) -> &mut Self {
+        self.special.set(ModeBits::SETGID, setgid);
+        self
+    }
+    /// sets or clears the sticky bit.
+    pub fn sticky(&mut self, sticky: bool) -> &mut Self {
+        self.special.set(ModeBits::STICKY, sticky);
         self
     }
     fn get_mode(&self) -> u32 {
-        // returns the mode from the octal parsed as integer number
-        // example "100644" will be parsed as 0o100644 and then parsed as u32 to get 33188
-        // this indeed will be compatible with the usage of the crate functionalities
-        u32::from_str_radix(&self.mode, 8).unwrap_or(0)
+        // every slot is already a number so they are simply combined into the mode
+        self.file_type.bits() | self.special.bits() | self.user << 6 | self.group << 3 | self.other
     }
     /// returns either [`FullPermission`](FullPermission) object or [`FullPermissionError`](FullPermissionError) error .
     pub fn build(&self) -> Result<FullPermission, FullPermissionError> {
@@ -190,6 +662,19 @@ impl FullPermissionBuilder {
     }
 }
 
+impl FullPermission {
+    /// returns a builder seeded with every part of this permission.
+    pub fn to_builder(&self) -> FullPermissionBuilder {
+        FullPermissionBuilder {
+            file_type: self.file_type,
+            special: self.special,
+            user: self.user.get_partial_mode(),
+            group: self.group.get_partial_mode(),
+            other: self.other.get_partial_mode(),
+        }
+    }
+}
+
 impl Default for FullPermissionBuilder {
     fn default() -> Self {
         FullPermissionBuilder::new()
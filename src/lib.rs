@@ -6,25 +6,35 @@
 //! a single method to the file permission from the metadata.
 //!# Permission formats and file kinds representation
 //! the file permission its octal mode consists of 6-digit number
-//! the first three digits are for the file kind:
+//! the first two digits are for the file kind:
 //! <div align="center">
 //!     <h1>file kinds</h1>
 //! </div>
 //! <div align="center">
 //!     <p>
 //!
+//! | digits | shortcut usage |      file kind   |
+//! |--------|----------------|------------------|
+//! | 10     |       -        |   regular file   |
+//! | 12     |       l        |       symlink    |
+//! | 02     |       c        | character device |
+//! | 06     |       b        |    block device  |
+//! | 01     |       p        |       fifo       |
+//! | 14     |       s        |       socket     |
+//! | 04     |       d        |      directory   |
+//!  </p>
+//!</div>
+//! the third digit holds the special bits, it is usually `0`:
+//! <div align="center">
+//!     <p>
 //!
-//!     | digits | shortcut usage |      file kind   |
-//!     |--------|----------------|------------------|
-//!     | 100    |       -        |   regular file   |
-//!     | 120    |       l        |       symlink    |
-//!     | 020    |       c        | character device |
-//!     | 060    |       b        |    block device  |
-//!     | 010    |       p        |       fifo       |
-//!     | 140    |       s        |       socket     |
-//!     | 040    |       d        |      directory   |
+//! | digit | special bit | shown in   | with `x` | without `x` |
+//! |-------|-------------|------------|----------|-------------|
+//! |   4   |   setuid    | user slot  |    s     |      S      |
+//! |   2   |   setgid    | group slot |    s     |      S      |
+//! |   1   |   sticky    | other slot |    t     |      T      |
 //!  </p>
-//!</div>              
+//!</div>
 //! the next three digits are for the user permission where each digit represents
 //! a permission from three characters:
 //!
@@ -33,30 +43,36 @@
 //! <div align="center">
 //!     <p>
 //!
-//!    | digits | permission |
-//!    |--------|------------|
-//!    |    0   |     ---    |
-//!    |    1   |     --x    |
-//!    |    2   |     -w-    |
-//!    |    3   |     -wx    |
-//!    |    4   |     r--    |
-//!    |    5   |     r-x    |
-//!    |    6   |     rw-    |
-//!    |    7   |     rwx    |
+//! | digits | permission |
+//! |--------|------------|
+//! |    0   |     ---    |
+//! |    1   |     --x    |
+//! |    2   |     -w-    |
+//! |    3   |     -wx    |
+//! |    4   |     r--    |
+//! |    5   |     r-x    |
+//! |    6   |     rw-    |
+//! |    7   |     rwx    |
 //!   </p>
 //! </div>
 //!
 //! if we have a file with permission `33188 decimal` = `100644 octal` = `-rw-r--r--`
 //! such that :
 //!
-//!    1)  -  :  file type
-//!    2) rw- :  user permission
-//!    3) r-- :  group permission
-//!    4) r-- :  other permission
+//! 1)  -  :  file type
+//! 2) rw- :  user permission
+//! 3) r-- :  group permission
+//! 4) r-- :  other permission
+//!
+//! and a setuid binary `35309 decimal` = `104755 octal` = `-rwsr-xr-x`.
+//!
+//! internally the mode is kept as plain integer bits, the permission and special bits are
+//! exposed as the [`ModeBits`] flags so they can be tested without going through strings.
 //!
 //! # Examples
 //! Import the `ModeParseS` trait which uses FullPermission struct under the hood
-//! for which its method will be used as an extension for the `fs::Metadata` type.\
+//! for which its method will be used as an extension for the `fs::Metadata`, `Path`,
+//! `PathBuf`, `File` and `DirEntry` types.\
 //! It's the safest way to use this API!
 //! ## Extension on `Metadata` type
 //! In most times you will use it like this:
@@ -72,6 +88,10 @@
 //!    let metadata = path.metadata()?;
 //!    let permission = metadata.convert_permission_to_string()?;
 //!    assert_eq!(permission, "-rw-r--r--");
+//!
+//!    // the path can be used directly, symbolic links are not followed.
+//!    let full_permission = path.get_full_permission()?;
+//!    assert_eq!(full_permission.mode_as_octal(), "100644");
 //!    Ok(())
 //! }
 //! ```
@@ -104,14 +124,18 @@
 //!    // returning the permission as decimal number.
 //!    let mode = full_permission.get_mode();
 //!    assert_eq!(mode, mode_number);
-//!    // returning the file type as char.
+//!    // returning the file type, it can be compared with its `ls` letter.
 //!    let file_type = full_permission.get_file_type();
 //!    assert_eq!(file_type, '-');
 //!
 //!    // returns the categories of owners.
-//!    let user = full_permission.get_user();
-//!    let group = full_permission.get_group();
-//!    let other = full_permission.get_other();
+//!    let user = full_permission.user();
+//!    let group = full_permission.group();
+//!    let other = full_permission.other();
+//!
+//!    // the owner kinds are modified through `&mut` access.
+//!    full_permission.user_mut().set_execute(true);
+//!    assert_eq!(full_permission.to_string(), "-rwxr--r--");
 //!    
 //!     Ok(())
 //! }
@@ -128,7 +152,7 @@
 //!
 //! fn main() -> Result<(), Box<dyn Error>> {
 //!
-//!    let mut full_permission = FullPermissionBuilder::new()
+//!    let full_permission = FullPermissionBuilder::new()
 //!                             .file_type('-')
 //!                             .user("rw-")
 //!                             .group("r--")
@@ -141,11 +165,45 @@
 //! }
 //! ```
 
+mod access;
+mod apply_mode;
 mod category_util;
+mod chmod_transaction;
 mod errors;
+mod file_type;
+mod find_perm;
 mod full_permission;
+mod listing_parser;
+mod long_listing;
 mod metadata_ext_mode_parser;
-use category_util::modeparser::ModeParser;
+mod mode_literal;
+mod operation;
+mod owner_class;
+mod perm_pattern;
+mod permission_ops;
+mod permission_snapshot;
+mod recursive_chmod;
+mod stat_format;
+mod symbolic_mode;
+mod umask;
+pub use access::{Identity, OwnedPermission};
+pub use apply_mode::{ApplyOptions, ApplyOutcome, TypeMismatch};
+pub use category_util::mode_bits::ModeBits;
+pub use category_util::modeparser::ModeParser;
+pub use category_util::owners::OwnersKind;
+pub use chmod_transaction::ChmodTransaction;
 pub use errors::permission_error::FullPermissionError;
+pub use file_type::{FileType, IntoFileType};
+pub use find_perm::{FindPerm, FindPermIter, PermMatch};
 pub use full_permission::{FullPermission, FullPermissionBuilder};
+pub use long_listing::{AccessMarker, Listing, ListingRow, ListingSize, LongListing, TimeStyle};
 pub use metadata_ext_mode_parser::PermStrParser;
+pub use mode_literal::ModeFormat;
+pub use operation::{Operation, Verdict};
+pub use owner_class::{Class, Perm, PermSet, WhoSet};
+pub use perm_pattern::PermPattern;
+pub use permission_snapshot::{PermissionSnapshot, SnapshotDiff, SnapshotEntry};
+pub use recursive_chmod::{ChmodChange, ChmodReport, ModeRule, RecursiveChmod, SymlinkPolicy};
+pub use stat_format::StatFormat;
+pub use symbolic_mode::SymbolicMode;
+pub use umask::Umask;
@@ -0,0 +1,324 @@
+//! This module contains the parser of the `ls -l` and `ls -ln` output of GNU and BSD `ls`
+//! back into [`Listing`] and [`ListingRow`] values.
+//!
+//! a row is split on its spaces up to the modification time, the time is recognized by
+//! its shape and everything after it is the name, so names with spaces are kept whole:
+//!
+//! ```text
+//! total 8
+//! crw-rw-rw-  1 root root 1, 3 Oct 17 09:36 null
+//! -rw-r--r--+ 1 1000 1000   10 2020-01-02 03:04 my notes.txt
+//! lrwxrwxrwx  1 root root    6 Jan  2  2020 current -> config
+//! ```
+//!
+
+use crate::category_util::accounts::Account;
+use crate::long_listing::{AccessMarker, Listing, ListingRow, ListingSize};
+use crate::{FileType, FullPermission, FullPermissionError};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy)]
+/// a cursor over the fields of a line separated by one or more spaces.
+struct Fields<'a> {
+    line: &'a str,
+    position: usize,
+}
+
+impl<'a> Fields<'a> {
+    /// returns the next field and the byte position it starts at.
+    fn next(&mut self) -> Option<(usize, &'a str)> {
+        let rest = &self.line[self.position..];
+        let start = self.position + (rest.len() - rest.trim_start_matches(' ').len());
+        let length = self.line[start..]
+            .find(' ')
+            .unwrap_or(self.line.len() - start);
+        if length == 0 {
+            return None;
+        }
+        self.position = start + length;
+        Some((start, &self.line[start..start + length]))
+    }
+    /// returns the next field without moving the cursor.
+    fn peek(&self) -> Option<(usize, &'a str)> {
+        let mut fields = *self;
+        fields.next()
+    }
+    /// moves the cursor to the next field if it matches.
+    fn next_if(&mut self, matches: impl Fn(&str) -> bool) -> Option<(usize, &'a str)> {
+        self.peek()
+            .filter(|(_, field)| matches(field))
+            .and_then(|_| self.next())
+    }
+}
+
+/// returns the row of a line with the given line number, starting at `1`.
+fn parse_row(line: usize, input: &str) -> Result<ListingRow, FullPermissionError> {
+    let invalid = |position: usize, reason: &str| FullPermissionError::InvalidLine {
+        line,
+        input: input.to_string(),
+        reason: format!("{reason} at position {position}"),
+    };
+    let mut fields = Fields {
+        line: input,
+        position: 0,
+    };
+    let (position, mode) = fields
+        .next()
+        .ok_or_else(|| invalid(0, "expected a permission"))?;
+    let permission: FullPermission = mode
+        .parse()
+        .map_err(|error: FullPermissionError| invalid(position, &error.to_string()))?;
+    let access_marker = mode.chars().nth(10).and_then(AccessMarker::from_symbol);
+
+    let end = fields.position;
+    let (position, links) = fields
+        .next()
+        .ok_or_else(|| invalid(end, "expected the link count"))?;
+    let links = links
+        .parse()
+        .map_err(|_| invalid(position, "the link count is not a number"))?;
+    let end = fields.position;
+    let (_, owner) = fields
+        .next()
+        .ok_or_else(|| invalid(end, "expected the owner"))?;
+    let end = fields.position;
+    let (_, group) = fields
+        .next()
+        .ok_or_else(|| invalid(end, "expected the group"))?;
+
+    let end = fields.position;
+    let (position, size) = fields
+        .next()
+        .ok_or_else(|| invalid(end, "expected the size"))?;
+    let size = match size.split_once(',') {
+        Some((major, minor)) => {
+            // GNU writes `1, 3` and a few systems `1,3`
+            let minor = match minor {
+                "" => fields.next().map_or("", |(_, minor)| minor),
+                minor => minor,
+            };
+            match (major.parse(), minor.parse()) {
+                (Ok(major), Ok(minor)) => ListingSize::Device { major, minor },
+                _ => return Err(invalid(position, "the device numbers are not numbers")),
+            }
+        }
+        None => ListingSize::Bytes(
+            size.parse()
+                .map_err(|_| invalid(position, "the size is not a number"))?,
+        ),
+    };
+
+    let end = fields.position;
+    let (start, end) =
+        parse_time(&mut fields).ok_or_else(|| invalid(end, "expected a modification time"))?;
+    let time = input[start..end].to_string();
+    let name = input[end..]
+        .strip_prefix(' ')
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| invalid(end, "expected a name"))?;
+    let (name, target) = match name.split_once(" -> ") {
+        Some((name, target)) if permission.get_file_type() == FileType::Symlink => {
+            (name, Some(target.to_string()))
+        }
+        _ => (name, None),
+    };
+    Ok(ListingRow {
+        permission,
+        access_marker,
+        links,
+        owner: parse_account(owner),
+        group: parse_account(group),
+        size,
+        time,
+        name: name.to_string(),
+        target,
+    })
+}
+
+/// reads the modification time and returns the byte range it covers.
+///
+/// the known forms are the GNU styles `Jan  2  2020`, `Oct 18 05:46`, `2020-01-02 03:04`,
+/// `2020-01-02 03:04:05.123456789 +0100`, `10-18 05:46` and `2020-01-02`, the BSD
+/// `Oct 18 05:46:12 2026` of `ls -lT`, and `18 Oct 05:46` of the locales that write the
+/// day first.
+fn parse_time(fields: &mut Fields) -> Option<(usize, usize)> {
+    let (start, first) = fields.next()?;
+    let (last_start, last) = if is_date(first, 3) {
+        match fields.next_if(is_clock) {
+            Some(clock) => fields.next_if(is_zone).unwrap_or(clock),
+            // the old files of the `iso` style are followed by a second space
+            None => {
+                let end = start + first.len();
+                return Some((
+                    start,
+                    end + usize::from(fields.line[end..].starts_with("  ")),
+                ));
+            }
+        }
+    } else if is_date(first, 2) {
+        fields.next_if(is_clock)?
+    } else if is_month(first) {
+        fields.next_if(is_day)?;
+        clock_or_year(fields)?
+    } else if is_day(first) {
+        fields.next_if(is_month)?;
+        clock_or_year(fields)?
+    } else {
+        return None;
+    };
+    Some((start, last_start + last.len()))
+}
+
+/// reads the time or the year that follows a month and a day, the seconds of BSD
+/// `ls -lT` are followed by the year.
+fn clock_or_year<'a>(fields: &mut Fields<'a>) -> Option<(usize, &'a str)> {
+    let year = |field: &str| field.len() == 4 && is_number(field);
+    match fields.next_if(is_clock) {
+        Some(clock) if clock.1.len() == 8 => fields.next_if(year).or(Some(clock)),
+        Some(clock) => Some(clock),
+        None => fields.next_if(year),
+    }
+}
+
+/// reads an owner or a group column, the text cannot tell a name made of digits from an
+/// id so such a column is read as an id.
+fn parse_account(field: &str) -> Account {
+    Account {
+        text: field.to_string(),
+        resolved: !is_number(field),
+    }
+}
+
+fn is_number(field: &str) -> bool {
+    !field.is_empty() && field.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// returns true for a date of `parts` numbers separated by dashes, `YYYY-MM-DD` or `MM-DD`.
+fn is_date(field: &str, parts: usize) -> bool {
+    let numbers: Vec<&str> = field.split('-').collect();
+    numbers.len() == parts && numbers.iter().all(|number| is_number(number))
+}
+
+/// returns true for `HH:MM`, `HH:MM:SS` and `HH:MM:SS.NNNNNNNNN`.
+fn is_clock(field: &str) -> bool {
+    let (clock, fraction) = field.split_once('.').unwrap_or((field, "0"));
+    let parts: Vec<&str> = clock.split(':').collect();
+    (2..=3).contains(&parts.len())
+        && parts.iter().all(|part| part.len() <= 2 && is_number(part))
+        && is_number(fraction)
+}
+
+/// returns true for a numeric time zone such as `+0100`.
+fn is_zone(field: &str) -> bool {
+    field.len() == 5 && field.starts_with(['+', '-']) && is_number(&field[1..])
+}
+
+fn is_day(field: &str) -> bool {
+    field.len() <= 2 && is_number(field)
+}
+
+/// returns true for an abbreviated month name of any locale, such as `Oct` or `oct.`.
+fn is_month(field: &str) -> bool {
+    field.chars().next().is_some_and(char::is_alphabetic)
+        && field.chars().all(|c| c.is_alphabetic() || c == '.')
+}
+
+/// parses a single line of `ls -l`, the errors name line `1`.
+///
+/// # Example
+/// ```
+/// use fmodeparser::{AccessMarker, ListingRow, ListingSize};
+/// # use fmodeparser::FullPermissionError;
+/// # fn main() -> Result<(), FullPermissionError> {
+///
+/// let row: ListingRow = "crw-rw-rw-+ 1 root tty 5, 0 Oct 17 09:36 tty".parse()?;
+/// assert_eq!(row.get_permission().mode_as_octal(), "020666");
+/// assert_eq!(row.get_access_marker(), Some(AccessMarker::Acl));
+/// assert_eq!(row.get_size(), ListingSize::Device { major: 5, minor: 0 });
+/// assert_eq!(row.get_time(), "Oct 17 09:36");
+///
+/// let row: ListingRow = "lrwxr-xr-x  1 0 0 11 Jan  2  2020 my link -> /etc/hosts".parse()?;
+/// assert_eq!(row.get_name(), "my link");
+/// assert_eq!(row.get_target(), Some("/etc/hosts"));
+/// # Ok(())
+/// # }
+/// ```
+impl FromStr for ListingRow {
+    type Err = FullPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_row(1, s)
+    }
+}
+
+impl TryFrom<&str> for ListingRow {
+    type Error = FullPermissionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// parses the output of `ls -l` for a file or a directory, the `total` line may only come
+/// first and the empty lines are skipped.
+///
+/// # Example
+/// ```
+/// use fmodeparser::Listing;
+/// # use fmodeparser::FullPermissionError;
+/// # fn main() -> Result<(), FullPermissionError> {
+///
+/// let output = "total 8\n\
+///     -rw-r--r--  1 alice  staff   10 Oct 18 05:46 config\n\
+///     drwxr-xr-x@ 3 alice  staff   96 Oct 18  2023 sub dir\n";
+/// let listing: Listing = output.parse()?;
+/// assert_eq!(listing.get_total(), Some(8));
+/// assert_eq!(listing.get_rows()[1].get_name(), "sub dir");
+///
+/// let error = "total 8\n-rw-r--r-- one alice staff 10 Oct 18 05:46 config"
+///     .parse::<Listing>()
+///     .unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "line 2 `-rw-r--r-- one alice staff 10 Oct 18 05:46 config` is not valid: \
+///      the link count is not a number at position 11"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+impl FromStr for Listing {
+    type Err = FullPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut listing = Listing::default();
+        for (index, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match line.strip_prefix("total ") {
+                Some(total) if listing.rows.is_empty() && listing.total.is_none() => {
+                    let total =
+                        total
+                            .trim()
+                            .parse()
+                            .map_err(|_| FullPermissionError::InvalidLine {
+                                line: index + 1,
+                                input: line.to_string(),
+                                reason: "the total is not a number".to_string(),
+                            })?;
+                    listing.total = Some(total);
+                }
+                _ => listing.rows.push(parse_row(index + 1, line)?),
+            }
+        }
+        Ok(listing)
+    }
+}
+
+impl TryFrom<&str> for Listing {
+    type Error = FullPermissionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
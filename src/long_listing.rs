@@ -0,0 +1,580 @@
+//! This module contains the `ls -l` long listing: the rows of a file or of the entries of a
+//! directory with the permission, the link count, the owner, the group, the size, the
+//! modification time and the name, written with the column alignment of GNU `ls`.
+//!
+
+use crate::category_util::accounts::Account;
+use crate::category_util::device::device_numbers;
+use crate::{FileType, FullPermission, FullPermissionError, PermStrParser};
+use std::ffi::{c_char, CString, OsString};
+use std::fmt::{Display, Formatter};
+use std::fs::Metadata;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// the age after which `ls` shows the year instead of the time, half of a Gregorian year.
+const SIX_MONTHS: i64 = 31_556_952 / 2;
+/// the largest buffer given to `strftime` before a format is considered empty.
+const MAX_TIME_LENGTH: usize = 4096;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
+/// the format of the modification time, like the `--time-style` option of GNU `ls`.
+///
+/// every style has a format for the files modified in the last six months and another one
+/// for the older files and the files in the future.
+pub enum TimeStyle {
+    /// `full-iso`: `2024-03-09 14:05:12.123456789 +0100`.
+    FullIso,
+    /// `long-iso`: `2024-03-09 14:05`.
+    LongIso,
+    /// `iso`: `03-09 14:05` for recent files and `2023-03-09 ` for old ones.
+    Iso,
+    /// `locale`: `Mar  9 14:05` for recent files and `Mar  9  2023` for old ones.
+    #[default]
+    Locale,
+    /// `+FORMAT`: a `strftime` format that also knows `%N` for the nanoseconds, a second
+    /// format for the recent files can follow a newline.
+    Format(String),
+}
+
+impl TimeStyle {
+    /// returns the formats of the old and of the recent files.
+    fn formats(&self) -> (&str, &str) {
+        match self {
+            TimeStyle::FullIso => ("%Y-%m-%d %H:%M:%S.%N %z", "%Y-%m-%d %H:%M:%S.%N %z"),
+            TimeStyle::LongIso => ("%Y-%m-%d %H:%M", "%Y-%m-%d %H:%M"),
+            TimeStyle::Iso => ("%Y-%m-%d ", "%m-%d %H:%M"),
+            TimeStyle::Locale => ("%b %e  %Y", "%b %e %H:%M"),
+            TimeStyle::Format(format) => format.split_once('\n').unwrap_or((format, format)),
+        }
+    }
+    /// returns the time in the local time zone, `now` tells the recent files from the old ones.
+    fn format(&self, seconds: i64, nanoseconds: u32, now: i64) -> String {
+        let (old, recent) = self.formats();
+        let is_recent = now - SIX_MONTHS < seconds && seconds <= now;
+        format_local_time(seconds, nanoseconds, if is_recent { recent } else { old })
+    }
+}
+
+/// parses the argument of `--time-style`.
+impl FromStr for TimeStyle {
+    type Err = FullPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full-iso" => Ok(TimeStyle::FullIso),
+            "long-iso" => Ok(TimeStyle::LongIso),
+            "iso" => Ok(TimeStyle::Iso),
+            "locale" => Ok(TimeStyle::Locale),
+            _ => match s.strip_prefix('+') {
+                Some(format) if !format.contains('\0') => Ok(TimeStyle::Format(format.into())),
+                _ => Err(FullPermissionError::InvalidTimeStyle {
+                    input: s.to_string(),
+                }),
+            },
+        }
+    }
+}
+
+/// writes the style as the argument of `--time-style`.
+impl Display for TimeStyle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeStyle::FullIso => write!(f, "full-iso"),
+            TimeStyle::LongIso => write!(f, "long-iso"),
+            TimeStyle::Iso => write!(f, "iso"),
+            TimeStyle::Locale => write!(f, "locale"),
+            TimeStyle::Format(format) => write!(f, "+{format}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+/// the marker `ls` writes after the permission when a file has more access rules than
+/// its mode.
+pub enum AccessMarker {
+    /// `+` the file has an access control list.
+    Acl,
+    /// `.` the file only has a security context, such as a SELinux label.
+    SecurityContext,
+    /// `@` the file has extended attributes, written by the BSD and macOS `ls`. it is read
+    /// from their listings but never produced by [`LongListing`].
+    ExtendedAttributes,
+}
+
+impl AccessMarker {
+    /// returns the character written after the permission.
+    pub fn symbol(&self) -> char {
+        match self {
+            AccessMarker::Acl => '+',
+            AccessMarker::SecurityContext => '.',
+            AccessMarker::ExtendedAttributes => '@',
+        }
+    }
+    /// returns the marker of a character, or `None` if it is not a marker.
+    pub fn from_symbol(symbol: char) -> Option<Self> {
+        match symbol {
+            '+' => Some(AccessMarker::Acl),
+            '.' => Some(AccessMarker::SecurityContext),
+            '@' => Some(AccessMarker::ExtendedAttributes),
+            _ => None,
+        }
+    }
+}
+
+impl Display for AccessMarker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+/// the size column of a row, the device numbers for character and block devices.
+pub enum ListingSize {
+    /// the size in bytes.
+    Bytes(u64),
+    /// the major and minor numbers of a device.
+    Device { major: u32, minor: u32 },
+}
+
+/// writes the size like a single row of `ls -l`: `4096` or `8, 1`.
+impl Display for ListingSize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ListingSize::Bytes(bytes) => write!(f, "{bytes}"),
+            ListingSize::Device { major, minor } => write!(f, "{major}, {minor}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+/// a single row of a long listing.
+pub struct ListingRow {
+    pub(crate) permission: FullPermission,
+    pub(crate) access_marker: Option<AccessMarker>,
+    pub(crate) links: u64,
+    pub(crate) owner: Account,
+    pub(crate) group: Account,
+    pub(crate) size: ListingSize,
+    pub(crate) time: String,
+    pub(crate) name: String,
+    pub(crate) target: Option<String>,
+}
+
+impl ListingRow {
+    /// returns the file type and mode of the first column.
+    pub fn get_permission(&self) -> FullPermission {
+        self.permission
+    }
+    /// returns the marker written after the permission, if any.
+    pub fn get_access_marker(&self) -> Option<AccessMarker> {
+        self.access_marker
+    }
+    /// returns the number of hard links.
+    pub fn get_links(&self) -> u64 {
+        self.links
+    }
+    /// returns the owner name, or the user id when it has no name or with `-n`.
+    pub fn get_owner(&self) -> &str {
+        &self.owner.text
+    }
+    /// returns the group name, or the group id when it has no name or with `-n`.
+    pub fn get_group(&self) -> &str {
+        &self.group.text
+    }
+    /// returns the size or the device numbers.
+    pub fn get_size(&self) -> ListingSize {
+        self.size
+    }
+    /// returns the modification time formatted with the time style of the listing.
+    pub fn get_time(&self) -> &str {
+        &self.time
+    }
+    /// returns the name of the entry, or the path as it was given for a single file.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    /// returns the target of a symbolic link.
+    pub fn get_target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+}
+
+/// writes the row without any column padding.
+impl Display for ListingRow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Widths::of(std::slice::from_ref(self)).write_row(f, self)
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+/// the rows of a long listing and the `total` line of a directory.
+///
+/// its [`Display`] writes the output of `ls -l` with the columns aligned like GNU `ls`:
+/// the numbers are aligned on the right, the names on the left, and the device numbers
+/// share the size column.
+pub struct Listing {
+    pub(crate) total: Option<u64>,
+    pub(crate) rows: Vec<ListingRow>,
+}
+
+impl Listing {
+    /// returns the disk usage of the entries in 1024-byte blocks, it is only given when the
+    /// entries of a directory are listed.
+    pub fn get_total(&self) -> Option<u64> {
+        self.total
+    }
+    /// returns the rows in the order they are written.
+    pub fn get_rows(&self) -> &[ListingRow] {
+        &self.rows
+    }
+}
+
+impl Display for Listing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(total) = self.total {
+            writeln!(f, "total {total}")?;
+        }
+        let widths = Widths::of(&self.rows);
+        for row in &self.rows {
+            widths.write_row(f, row)?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+/// the widths of the aligned columns of a set of rows.
+struct Widths {
+    access_marker: bool,
+    links: usize,
+    owner: usize,
+    group: usize,
+    size: usize,
+    major: usize,
+    minor: usize,
+}
+
+impl Widths {
+    fn of(rows: &[ListingRow]) -> Self {
+        let mut widths = Widths::default();
+        for row in rows {
+            widths.access_marker |= row.access_marker.is_some();
+            widths.links = widths.links.max(row.links.to_string().len());
+            widths.owner = widths.owner.max(row.owner.text.chars().count());
+            widths.group = widths.group.max(row.group.text.chars().count());
+            match row.size {
+                ListingSize::Bytes(bytes) => {
+                    widths.size = widths.size.max(bytes.to_string().len());
+                }
+                ListingSize::Device { major, minor } => {
+                    widths.major = widths.major.max(major.to_string().len());
+                    widths.minor = widths.minor.max(minor.to_string().len());
+                    widths.size = widths.size.max(widths.major + 2 + widths.minor);
+                }
+            }
+        }
+        widths
+    }
+    fn write_row(&self, f: &mut Formatter<'_>, row: &ListingRow) -> std::fmt::Result {
+        write!(f, "{}", row.permission)?;
+        match row.access_marker {
+            Some(marker) => write!(f, "{marker}")?,
+            None if self.access_marker => write!(f, " ")?,
+            None => {}
+        }
+        write!(f, " {:>width$} ", row.links, width = self.links)?;
+        write_account(f, &row.owner, self.owner)?;
+        write_account(f, &row.group, self.group)?;
+        match row.size {
+            ListingSize::Bytes(bytes) => write!(f, "{bytes:>width$}", width = self.size)?,
+            ListingSize::Device { major, minor } => write!(
+                f,
+                "{major:>major_width$}, {minor:>minor_width$}",
+                major_width = self.size - 2 - self.minor,
+                minor_width = self.minor
+            )?,
+        }
+        write!(f, " {} {}", row.time, row.name)?;
+        if let Some(target) = &row.target {
+            write!(f, " -> {target}")?;
+        }
+        Ok(())
+    }
+}
+
+/// writes an owner or a group column, the ids without a name are aligned on the right.
+fn write_account(f: &mut Formatter<'_>, account: &Account, width: usize) -> std::fmt::Result {
+    let text = &account.text;
+    if account.resolved {
+        write!(f, "{text:<width$} ")
+    } else {
+        write!(f, "{text:>width$} ")
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// builds the long listing of a file or of the entries of a directory like `ls -l`.
+///
+/// # Example
+/// ```
+/// use fmodeparser::{FullPermission, LongListing, TimeStyle};
+/// # use fmodeparser::FullPermissionError;
+/// # fn main() -> Result<(), FullPermissionError> {
+/// # let directory = tempfile::tempdir()?;
+/// # let root = directory.path();
+/// # std::fs::write(root.join("config"), "port = 80\n")?;
+/// # FullPermission::new(0o100640)?.apply_to(root.join("config"))?;
+/// # std::os::unix::fs::symlink("config", root.join("current"))?;
+///
+/// let listing = LongListing::new()
+///     .numeric_ids(true)
+///     .time_style("+%s".parse()?)
+///     .list(root)?;
+/// let rows = listing.get_rows();
+/// assert_eq!(rows.len(), 2);
+/// assert_eq!(rows[0].get_permission().to_string(), "-rw-r-----");
+/// assert_eq!(rows[0].get_size().to_string(), "10");
+/// assert_eq!(rows[1].get_name(), "current");
+/// assert_eq!(rows[1].get_target(), Some("config"));
+///
+/// let text = listing.to_string();
+/// assert!(text.starts_with("total "));
+/// assert!(text.lines().nth(2).unwrap().ends_with(" current -> config"));
+/// # Ok(())
+/// # }
+/// ```
+pub struct LongListing {
+    numeric_ids: bool,
+    all: bool,
+    directory: bool,
+    time_style: TimeStyle,
+    now: Option<SystemTime>,
+}
+
+impl LongListing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// writes the user and group ids instead of their names, like `-n`.
+    pub fn numeric_ids(mut self, numeric_ids: bool) -> Self {
+        self.numeric_ids = numeric_ids;
+        self
+    }
+    /// lists the entries starting with `.` as well as `.` and `..`, like `-a`.
+    pub fn all(mut self, all: bool) -> Self {
+        self.all = all;
+        self
+    }
+    /// lists a directory itself instead of its entries, like `-d`.
+    pub fn directory(mut self, directory: bool) -> Self {
+        self.directory = directory;
+        self
+    }
+    /// sets the format of the modification time, like `--time-style`.
+    pub fn time_style(mut self, time_style: TimeStyle) -> Self {
+        self.time_style = time_style;
+        self
+    }
+    /// sets the time used to tell the recent files from the old ones, it defaults to the
+    /// time the listing is made.
+    pub fn now(mut self, now: SystemTime) -> Self {
+        self.now = Some(now);
+        self
+    }
+    /// lists the path, a symbolic link is listed itself and is never followed.
+    ///
+    /// a directory gets a row for each of its entries sorted by name and a `total` line,
+    /// any other file and a directory listed with [`directory`](LongListing::directory) get
+    /// a single row named after the path as it was given.
+    pub fn list(&self, path: impl AsRef<Path>) -> Result<Listing, FullPermissionError> {
+        let path = path.as_ref();
+        let now = unix_time(self.now.unwrap_or_else(SystemTime::now));
+        let metadata = path
+            .symlink_metadata()
+            .map_err(|error| FullPermissionError::io(path, error))?;
+        if self.directory || !metadata.is_dir() {
+            let name = path.as_os_str().to_string_lossy().into_owned();
+            return Ok(Listing {
+                total: None,
+                rows: vec![self.row(path, name, &metadata, now)?],
+            });
+        }
+        let mut names = Vec::new();
+        for entry in
+            std::fs::read_dir(path).map_err(|error| FullPermissionError::io(path, error))?
+        {
+            let name = entry
+                .map_err(|error| FullPermissionError::io(path, error))?
+                .file_name();
+            if self.all || !name.as_bytes().starts_with(b".") {
+                names.push(name);
+            }
+        }
+        if self.all {
+            names.extend([OsString::from("."), OsString::from("..")]);
+        }
+        names.sort();
+        let mut blocks = 0;
+        let mut rows = Vec::with_capacity(names.len());
+        for name in names {
+            let child = path.join(&name);
+            let metadata = child
+                .symlink_metadata()
+                .map_err(|error| FullPermissionError::io(&child, error))?;
+            blocks += metadata.blocks();
+            let name = name.to_string_lossy().into_owned();
+            rows.push(self.row(&child, name, &metadata, now)?);
+        }
+        // `st_blocks` counts 512-byte blocks and `ls` rounds the total up to 1024-byte blocks
+        Ok(Listing {
+            total: Some(blocks.div_ceil(2)),
+            rows,
+        })
+    }
+    fn row(
+        &self,
+        path: &Path,
+        name: String,
+        metadata: &Metadata,
+        now: i64,
+    ) -> Result<ListingRow, FullPermissionError> {
+        let permission = metadata.get_full_permission()?;
+        let (owner, group) = if self.numeric_ids {
+            (Account::id(metadata.uid()), Account::id(metadata.gid()))
+        } else {
+            (
+                Account::user(metadata.uid()),
+                Account::group(metadata.gid()),
+            )
+        };
+        let size = match permission.get_file_type() {
+            FileType::CharDevice | FileType::BlockDevice => {
+                let (major, minor) = device_numbers(metadata.rdev());
+                ListingSize::Device { major, minor }
+            }
+            _ => ListingSize::Bytes(metadata.len()),
+        };
+        let target = if permission.get_file_type() == FileType::Symlink {
+            let target =
+                std::fs::read_link(path).map_err(|error| FullPermissionError::io(path, error))?;
+            Some(target.as_os_str().to_string_lossy().into_owned())
+        } else {
+            None
+        };
+        Ok(ListingRow {
+            permission,
+            access_marker: access_marker(path),
+            links: metadata.nlink(),
+            owner,
+            group,
+            size,
+            time: self.time_style.format(
+                metadata.mtime(),
+                u32::try_from(metadata.mtime_nsec()).unwrap_or_default(),
+                now,
+            ),
+            name,
+            target,
+        })
+    }
+}
+
+/// returns the seconds since the epoch, negative before it.
+fn unix_time(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => i64::try_from(duration.as_secs()).unwrap_or(i64::MAX),
+        Err(error) => i64::try_from(error.duration().as_secs()).map_or(i64::MIN, |s| -s),
+    }
+}
+
+/// returns the marker of the access control list or of the security context of a file,
+/// read from the names of its extended attributes.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn access_marker(path: &Path) -> Option<AccessMarker> {
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    // SAFETY: `path` is nul terminated, a null buffer of size 0 only asks for the size.
+    let size = unsafe { libc::llistxattr(path.as_ptr(), std::ptr::null_mut(), 0) };
+    let mut names = vec![0u8; usize::try_from(size).ok().filter(|&size| size > 0)?];
+    // SAFETY: the buffer is valid for `names.len()` bytes during the call.
+    let size = unsafe {
+        libc::llistxattr(
+            path.as_ptr(),
+            names.as_mut_ptr().cast::<c_char>(),
+            names.len(),
+        )
+    };
+    names.truncate(usize::try_from(size).ok()?);
+    let names: Vec<&[u8]> = names.split(|&byte| byte == 0).collect();
+    if names
+        .iter()
+        .any(|name| *name == b"system.posix_acl_access" || *name == b"system.posix_acl_default")
+    {
+        Some(AccessMarker::Acl)
+    } else if names.contains(&&b"security.selinux"[..]) {
+        Some(AccessMarker::SecurityContext)
+    } else {
+        None
+    }
+}
+
+/// returns no marker, the other systems name their extended attributes differently and
+/// their access control lists are not stored in them.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn access_marker(_path: &Path) -> Option<AccessMarker> {
+    None
+}
+
+/// formats a time of the local time zone with `strftime`, `%N` is replaced by the
+/// nanoseconds first since `strftime` does not know it.
+fn format_local_time(seconds: i64, nanoseconds: u32, format: &str) -> String {
+    let mut expanded = String::with_capacity(format.len());
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('%', Some('N')) => {
+                expanded.push_str(&format!("{nanoseconds:09}"));
+                chars.next();
+            }
+            ('%', Some('%')) => {
+                expanded.push_str("%%");
+                chars.next();
+            }
+            _ => expanded.push(c),
+        }
+    }
+    let Ok(format) = CString::new(expanded) else {
+        return String::new();
+    };
+    let time = seconds as libc::time_t;
+    // SAFETY: a zeroed `tm` is a valid value that `localtime_r` fills.
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    // SAFETY: both pointers are valid during the call.
+    if unsafe { libc::localtime_r(&time, &mut tm) }.is_null() {
+        // `ls` writes the seconds when the time cannot be broken down
+        return seconds.to_string();
+    }
+    let mut size = 64;
+    loop {
+        let mut buffer = vec![0u8; size];
+        // SAFETY: the buffer is valid for `size` bytes and the format is nul terminated.
+        let length = unsafe {
+            libc::strftime(
+                buffer.as_mut_ptr().cast::<c_char>(),
+                size,
+                format.as_ptr(),
+                &tm,
+            )
+        };
+        // `strftime` returns 0 both for an empty result and for a too small buffer
+        if length > 0 || size >= MAX_TIME_LENGTH {
+            buffer.truncate(length);
+            return String::from_utf8_lossy(&buffer).into_owned();
+        }
+        size *= 4;
+    }
+}
@@ -1,10 +1,42 @@
-use crate::FullPermission;
-use std::error::Error;
-use std::fs::Metadata;
+use crate::{FileType, FullPermission, FullPermissionError};
+use std::fs::{DirEntry, File, Metadata};
 use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
 
-/// this trait is used as an extension on [Metadata](Metadata) to parse the permission of a file.
+/// this trait is used as an extension on [Metadata](Metadata), [Path](Path), [PathBuf](PathBuf),
+/// [File](File) and [DirEntry](DirEntry) to parse the permission of a file.
+///
+/// symbolic links are not followed by default so the permission of a link is the one of the
+/// link itself like `ls -l` shows it, the `*_following` methods read the file the link
+/// points to instead.
 pub trait PermStrParser {
+    /// returns the raw mode of the file, the symbolic links are only followed when
+    /// `follow_symlinks` is `true`.
+    ///
+    /// an already read [`Metadata`] is returned as it is whatever `follow_symlinks` is, and an
+    /// open [`File`] is always the file itself.
+    fn read_mode(&self, follow_symlinks: bool) -> Result<u32, FullPermissionError>;
+    /// returns the permission of the file without following symbolic links.
+    /// # Example
+    /// ```
+    /// use std::path::Path;
+    /// use fmodeparser::{FileType, PermStrParser};
+    /// # use fmodeparser::FullPermissionError;
+    /// # fn main() -> Result<(), FullPermissionError> {
+    ///
+    /// let permission = Path::new("out.txt").get_full_permission()?;
+    /// assert_eq!(permission.get_file_type(), FileType::Regular);
+    /// assert_eq!(permission.mode_as_octal(), "100644");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_full_permission(&self) -> Result<FullPermission, FullPermissionError> {
+        FullPermission::new(self.read_mode(false)?)
+    }
+    /// returns the permission of the file, following symbolic links to the file they point to.
+    fn get_full_permission_following(&self) -> Result<FullPermission, FullPermissionError> {
+        FullPermission::new(self.read_mode(true)?)
+    }
     /// converts the permission number into a string representation.
     /// # Example
     /// ```
@@ -12,19 +44,76 @@ pub trait PermStrParser {
     /// use std::os::unix::fs::MetadataExt;
     /// use std::path::Path;
     /// use fmodeparser::PermStrParser;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
     ///
     /// let path = Path::new("out.txt");
-    /// let permission = path.convert_permission_to_string()?;
+    /// let permission = path.metadata()?.convert_permission_to_string()?;
     /// assert_eq!(permission, "-rw-r--r--");
+    /// assert_eq!(path.convert_permission_to_string()?, "-rw-r--r--");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn convert_permission_to_string(&self) -> Result<String, FullPermissionError> {
+        Ok(self.get_full_permission()?.to_string())
+    }
+    /// returns the file type stored in the mode, unknown type bits are kept as
+    /// [`FileType::Unknown`].
+    /// # Example
+    /// ```
+    /// use std::path::Path;
+    /// use fmodeparser::{FileType, PermStrParser};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
     ///
+    /// let metadata = Path::new("src").metadata()?;
+    /// assert_eq!(metadata.convert_file_type()?, FileType::Directory);
+    /// # Ok(())
+    /// # }
     /// ```
-    fn convert_permission_to_string(&self) -> Result<String, Box<dyn Error>>;
+    fn convert_file_type(&self) -> Result<FileType, FullPermissionError> {
+        Ok(FileType::from_bits(self.read_mode(false)?))
+    }
 }
 
 impl PermStrParser for Metadata {
-    fn convert_permission_to_string(&self) -> Result<String, Box<dyn Error>> {
-        let mode = self.mode();
-        let permission = FullPermission::new(mode)?;
-        Ok(permission.to_string())
+    fn read_mode(&self, _follow_symlinks: bool) -> Result<u32, FullPermissionError> {
+        Ok(self.mode())
+    }
+}
+
+impl PermStrParser for Path {
+    fn read_mode(&self, follow_symlinks: bool) -> Result<u32, FullPermissionError> {
+        let metadata = if follow_symlinks {
+            self.metadata()
+        } else {
+            self.symlink_metadata()
+        };
+        metadata
+            .map(|metadata| metadata.mode())
+            .map_err(|error| FullPermissionError::io(self, error))
+    }
+}
+
+impl PermStrParser for PathBuf {
+    fn read_mode(&self, follow_symlinks: bool) -> Result<u32, FullPermissionError> {
+        self.as_path().read_mode(follow_symlinks)
+    }
+}
+
+impl PermStrParser for File {
+    fn read_mode(&self, _follow_symlinks: bool) -> Result<u32, FullPermissionError> {
+        Ok(self.metadata()?.mode())
+    }
+}
+
+impl PermStrParser for DirEntry {
+    fn read_mode(&self, follow_symlinks: bool) -> Result<u32, FullPermissionError> {
+        if follow_symlinks {
+            return self.path().read_mode(true);
+        }
+        self.metadata()
+            .map(|metadata| metadata.mode())
+            .map_err(|error| FullPermissionError::io(self.path(), error))
     }
 }
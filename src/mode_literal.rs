@@ -0,0 +1,180 @@
+//! This module contains the flexible mode literal parser that auto-detects whether a
+//! string is a decimal mode, an octal mode, a symbolic permission or a chmod-style
+//! expression.
+//!
+
+use crate::{FileType, FullPermission, FullPermissionError, SymbolicMode};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+/// the interpretation chosen by [`FullPermission::parse_any`], also used as a hint to
+/// force one interpretation with [`FullPermission::parse_any_with_hint`].
+pub enum ModeFormat {
+    /// a decimal number like `33188` as returned by `MetadataExt::mode`.
+    Decimal,
+    /// an octal number like `644`, `0755`, `0o4755` or `100644`.
+    Octal,
+    /// a 9 or 10 character permission like `rwxr-x---` or `drwxr-xr-x`.
+    Symbolic,
+    /// a chmod-style assignment list like `u=rw,go=r`, applied to an empty regular file.
+    Expression,
+}
+
+impl Display for ModeFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ModeFormat::Decimal => "decimal",
+            ModeFormat::Octal => "octal",
+            ModeFormat::Symbolic => "symbolic",
+            ModeFormat::Expression => "expression",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FullPermission {
+    /// parses a mode written in any of the usual human forms and returns the permission
+    /// together with the interpretation that was chosen.
+    ///
+    /// the accepted forms are:
+    /// * octal with a `0o` or `0` prefix: `0o755`, `0644`.
+    /// * bare octal digits: `644`, `4755`, `100644`.
+    /// * decimal numbers: `33188`.
+    /// * symbolic permissions of 9 or 10 characters: `rwxr-x---`, `drwxr-xr-x`.
+    /// * chmod-style assignment lists: `u=rw,go=r`.
+    ///
+    /// bare digits are read as octal unless that reading is not a valid mode or the decimal
+    /// reading is the only sensible one. When both readings are sensible permissions,
+    /// like `420` (decimal `420` is octal `644`), an error is returned and
+    /// [`parse_any_with_hint`](FullPermission::parse_any_with_hint) must be used instead.
+    ///
+    /// # Example
+    /// ```
+    /// use fmodeparser::{FullPermission, ModeFormat};
+    /// # use fmodeparser::FullPermissionError;
+    /// # fn main() -> Result<(), FullPermissionError> {
+    ///
+    /// let (permission, format) = FullPermission::parse_any("0o755")?;
+    /// assert_eq!(format, ModeFormat::Octal);
+    /// assert_eq!(permission.to_string(), "-rwxr-xr-x");
+    ///
+    /// let (permission, format) = FullPermission::parse_any("u=rw,go=r")?;
+    /// assert_eq!(format, ModeFormat::Expression);
+    /// assert_eq!(permission.to_string(), "-rw-r--r--");
+    ///
+    /// assert!(FullPermission::parse_any("420").is_err());
+    /// let (permission, _) = FullPermission::parse_any_with_hint("420", ModeFormat::Decimal)?;
+    /// assert_eq!(permission.to_string(), "-rw-r--r--");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse_any(input: &str) -> Result<(FullPermission, ModeFormat), FullPermissionError> {
+        let input = input.trim();
+        let format = detect_format(input)?;
+        Self::parse_any_with_hint(input, format)
+    }
+    /// parses a mode forcing the given interpretation, this resolves the inputs that
+    /// [`parse_any`](FullPermission::parse_any) reports as ambiguous.
+    pub fn parse_any_with_hint(
+        input: &str,
+        hint: ModeFormat,
+    ) -> Result<(FullPermission, ModeFormat), FullPermissionError> {
+        let input = input.trim();
+        let permission = match hint {
+            ModeFormat::Decimal => FullPermission::new(parse_number(input, 10)?)?,
+            ModeFormat::Octal => {
+                let digits = input.strip_prefix("0o").unwrap_or(input);
+                FullPermission::new(parse_number(digits, 8)?)?
+            }
+            ModeFormat::Symbolic => match input.chars().count() {
+                9 => format!("-{input}").parse()?,
+                _ => input.parse()?,
+            },
+            ModeFormat::Expression => {
+                let expression: SymbolicMode = input.parse()?;
+                FullPermission::new(expression.apply_to_mode(FileType::Regular.bits(), 0))?
+            }
+        };
+        Ok((permission, hint))
+    }
+}
+
+/// parses a number in the given radix or returns an error naming the radix.
+fn parse_number(input: &str, radix: u32) -> Result<u32, FullPermissionError> {
+    u32::from_str_radix(input, radix).map_err(|source| FullPermissionError::InvalidNumber {
+        input: input.to_string(),
+        radix,
+        source,
+    })
+}
+
+/// returns the interpretation of the input without parsing it completely.
+fn detect_format(input: &str) -> Result<ModeFormat, FullPermissionError> {
+    if input.is_empty() {
+        return Err(FullPermissionError::UnrecognizedMode {
+            input: input.to_string(),
+        });
+    }
+    if input.starts_with("0o") {
+        return Ok(ModeFormat::Octal);
+    }
+    if input.chars().all(|c| c.is_ascii_digit()) {
+        return detect_numeric_format(input);
+    }
+    let is_symbolic = input.chars().all(|c| "-rwxsStTdlcbp+.@".contains(c));
+    match input.chars().count() {
+        9..=11 if is_symbolic => Ok(ModeFormat::Symbolic),
+        _ => Ok(ModeFormat::Expression),
+    }
+}
+
+/// decides between the decimal and the octal reading of a string made only of digits.
+fn detect_numeric_format(input: &str) -> Result<ModeFormat, FullPermissionError> {
+    // a leading zero is the usual way to write octal numbers
+    if input.len() > 1 && input.starts_with('0') {
+        return Ok(ModeFormat::Octal);
+    }
+    let octal = u32::from_str_radix(input, 8)
+        .ok()
+        .filter(|&mode| is_octal_sensible(mode));
+    let decimal = input
+        .parse::<u32>()
+        .ok()
+        .filter(|&mode| is_decimal_sensible(mode));
+    match (octal, decimal) {
+        (Some(octal), Some(decimal)) if octal != decimal => {
+            Err(FullPermissionError::AmbiguousMode {
+                input: input.to_string(),
+                octal,
+                decimal,
+            })
+        }
+        (Some(_), _) => Ok(ModeFormat::Octal),
+        (None, Some(_)) => Ok(ModeFormat::Decimal),
+        (None, None) => Err(FullPermissionError::UnrecognizedMode {
+            input: input.to_string(),
+        }),
+    }
+}
+
+/// returns `true` if the file type bits of the mode are empty or known.
+fn has_known_file_type(mode: u32) -> bool {
+    mode < 0o200000 && !FileType::from_bits(mode).is_unknown()
+}
+
+/// an octal reading is sensible when it has a known file type, humans write any bits in octal.
+fn is_octal_sensible(mode: u32) -> bool {
+    has_known_file_type(mode)
+}
+
+/// a decimal reading is sensible only when it looks like the mode of a real file: a known
+/// file type, no special bits, and every class having a subset of the bits of the one
+/// before it using only the conventional `0`, `4`, `5`, `6` and `7` digits.
+fn is_decimal_sensible(mode: u32) -> bool {
+    if !has_known_file_type(mode) || mode & 0o7000 != 0 {
+        return false;
+    }
+    let classes = [(mode >> 6) & 0o7, (mode >> 3) & 0o7, mode & 0o7];
+    let conventional = classes.iter().all(|digit| matches!(digit, 0 | 4..=7));
+    conventional && classes.windows(2).all(|pair| pair[1] & !pair[0] == 0)
+}
@@ -0,0 +1,207 @@
+//! This module contains the checks of the operations on files that need more than the
+//! permission of a single file: creating, deleting and renaming an entry of a directory, and
+//! executing a program or a script.
+//!
+
+use crate::{FileType, Identity, OwnedPermission, Perm, PermSet};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+/// an operation checked by [`Identity::check`].
+pub enum Operation {
+    /// creates an entry in the directory, it needs search and write on the directory.
+    Create { directory: OwnedPermission },
+    /// deletes the file from the directory, it needs search and write on the directory and,
+    /// when the directory has the sticky bit, to own the file or the directory.
+    Delete {
+        directory: OwnedPermission,
+        file: OwnedPermission,
+    },
+    /// renames the file inside the directory, like a delete of the file and of the entry
+    /// it replaces, if any.
+    Rename {
+        directory: OwnedPermission,
+        file: OwnedPermission,
+        replaced: Option<OwnedPermission>,
+    },
+    /// executes a compiled program, it needs execute on the regular file.
+    Execute { file: OwnedPermission },
+    /// executes an interpreted script, the interpreter also has to read it so it needs read
+    /// and execute on the regular file.
+    ExecuteScript { file: OwnedPermission },
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+/// the answer of a check: whether the operation is allowed and why.
+///
+/// a denial names the bit that blocks the operation, such as the group write bit of the
+/// directory or its sticky bit.
+pub struct Verdict {
+    allowed: bool,
+    reason: String,
+}
+
+impl Verdict {
+    fn allow(reason: impl Into<String>) -> Self {
+        Self {
+            allowed: true,
+            reason: reason.into(),
+        }
+    }
+    fn deny(reason: impl Into<String>) -> Self {
+        Self {
+            allowed: false,
+            reason: reason.into(),
+        }
+    }
+    /// returns `true` if the operation is allowed.
+    pub fn is_allowed(&self) -> bool {
+        self.allowed
+    }
+    /// returns the reason of the verdict.
+    pub fn get_reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+/// writes `allowed: ` or `denied: ` followed by the reason.
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let verdict = if self.allowed { "allowed" } else { "denied" };
+        write!(f, "{verdict}: {}", self.reason)
+    }
+}
+
+impl Identity {
+    /// checks an operation like the kernel does, the first missing permission is reported.
+    ///
+    /// # Example
+    /// ```
+    /// use fmodeparser::{Identity, Operation, OwnedPermission};
+    /// # use fmodeparser::FullPermissionError;
+    /// # fn main() -> Result<(), FullPermissionError> {
+    ///
+    /// let tmp = OwnedPermission::new("drwxrwxrwt".parse()?, 0, 0);
+    /// let file = OwnedPermission::new("-rw-rw-rw-".parse()?, 1000, 1000);
+    /// let other = Identity::new(1001, 1001);
+    ///
+    /// assert!(other.check(&Operation::Create { directory: tmp }).is_allowed());
+    /// let verdict = other.check(&Operation::Delete { directory: tmp, file });
+    /// assert!(!verdict.is_allowed());
+    /// assert_eq!(
+    ///     verdict.to_string(),
+    ///     "denied: the directory `drwxrwxrwt` has the sticky bit (`+t`) \
+    ///      and uid 1001 owns neither the file nor the directory"
+    /// );
+    ///
+    /// let script = OwnedPermission::new("-rwx--x--x".parse()?, 1000, 1000);
+    /// assert!(other.check(&Operation::Execute { file: script }).is_allowed());
+    /// assert_eq!(
+    ///     other.check(&Operation::ExecuteScript { file: script }).get_reason(),
+    ///     "the file `-rwx--x--x` lacks the other read bit (`o+r`)"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn check(&self, operation: &Operation) -> Verdict {
+        let outcome = match operation {
+            Operation::Create { directory } => self.modify_entries(directory),
+            Operation::Delete { directory, file } => self
+                .modify_entries(directory)
+                .and_then(|()| self.sticky_owner(directory, file)),
+            Operation::Rename {
+                directory,
+                file,
+                replaced,
+            } => self
+                .modify_entries(directory)
+                .and_then(|()| self.sticky_owner(directory, file))
+                .and_then(|()| match replaced {
+                    Some(replaced) => self.sticky_owner(directory, replaced),
+                    None => Ok(()),
+                }),
+            Operation::Execute { file } => self.execute(file, &[Perm::Execute]),
+            Operation::ExecuteScript { file } => self.execute(file, &[Perm::Execute, Perm::Read]),
+        };
+        match outcome {
+            Err(reason) => Verdict::deny(reason),
+            Ok(()) if self.is_root() => {
+                Verdict::allow("root is not limited by the permission bits")
+            }
+            Ok(()) => Verdict::allow(match operation {
+                Operation::Create { .. } => "search and write are granted on the directory",
+                Operation::Delete { .. } | Operation::Rename { .. } => {
+                    "search and write are granted on the directory and no sticky bit blocks it"
+                }
+                Operation::Execute { .. } => "execute is granted on the file",
+                Operation::ExecuteScript { .. } => "read and execute are granted on the file",
+            }),
+        }
+    }
+    /// checks the search and write permission needed to change the entries of a directory.
+    fn modify_entries(&self, directory: &OwnedPermission) -> Result<(), String> {
+        let permission = directory.get_permission();
+        if permission.get_file_type() != FileType::Directory {
+            return Err(format!("the parent `{permission}` is not a directory"));
+        }
+        self.require(directory, "directory", &[Perm::Execute, Perm::Write])
+    }
+    /// checks the sticky bit rule: only the owner of the file, the owner of the directory
+    /// and root can remove an entry.
+    fn sticky_owner(
+        &self,
+        directory: &OwnedPermission,
+        file: &OwnedPermission,
+    ) -> Result<(), String> {
+        let permission = directory.get_permission();
+        if !permission.get_sticky()
+            || self.is_root()
+            || self.get_uid() == file.get_uid()
+            || self.get_uid() == directory.get_uid()
+        {
+            return Ok(());
+        }
+        Err(format!(
+            "the directory `{permission}` has the sticky bit (`+t`) and uid {} owns neither \
+             the file nor the directory",
+            self.get_uid()
+        ))
+    }
+    /// checks the permissions needed to execute a regular file.
+    fn execute(&self, file: &OwnedPermission, perms: &[Perm]) -> Result<(), String> {
+        let permission = file.get_permission();
+        if permission.get_file_type() != FileType::Regular {
+            return Err(format!("the file `{permission}` is not a regular file"));
+        }
+        self.require(file, "file", perms)
+    }
+    /// checks the permissions in order and names the bit of the first one missing.
+    fn require(&self, target: &OwnedPermission, name: &str, perms: &[Perm]) -> Result<(), String> {
+        let permission = target.get_permission();
+        let granted = self.granted(target);
+        let Some(&perm) = perms
+            .iter()
+            .find(|&&perm| !granted.contains(PermSet::from(perm)))
+        else {
+            return Ok(());
+        };
+        if self.is_root() {
+            // root only misses the execute permission of a file nobody can execute
+            return Err(format!(
+                "the {name} `{permission}` has no execute bit (`a+x`) in any class, root needs one"
+            ));
+        }
+        let class = self.class_for(target);
+        // the execute permission of a directory lets its entries be searched
+        let perm_name =
+            if perm == Perm::Execute && permission.get_file_type() == FileType::Directory {
+                "search"
+            } else {
+                perm.name()
+            };
+        Err(format!(
+            "the {name} `{permission}` lacks the {} {perm_name} bit (`{class}+{perm}`)",
+            class.name()
+        ))
+    }
+}
@@ -0,0 +1,224 @@
+//! This module contains the typed owner classes and permissions used to change several
+//! owner kinds of a [`FullPermission`](crate::FullPermission) at once.
+//!
+
+use crate::FullPermissionError;
+use bitflags::bitflags;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+/// one of the three owner classes of a permission.
+pub enum Class {
+    User,
+    Group,
+    Other,
+}
+
+impl Class {
+    /// the three classes in the order they are shown by `ls -l`.
+    pub const ALL: [Class; 3] = [Class::User, Class::Group, Class::Other];
+
+    /// returns the shift of the class octal digit inside the mode (`6`, `3` or `0`).
+    pub fn shift(&self) -> u32 {
+        match self {
+            Class::User => 6,
+            Class::Group => 3,
+            Class::Other => 0,
+        }
+    }
+    /// returns the name of the class: `user`, `group` or `other`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Class::User => "user",
+            Class::Group => "group",
+            Class::Other => "other",
+        }
+    }
+    /// returns the `chmod` letter of the class: `u`, `g` or `o`.
+    pub fn symbol(&self) -> char {
+        match self {
+            Class::User => 'u',
+            Class::Group => 'g',
+            Class::Other => 'o',
+        }
+    }
+}
+
+impl Display for Class {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+/// one of the three permissions of an owner class.
+pub enum Perm {
+    Read,
+    Write,
+    Execute,
+}
+
+impl Perm {
+    /// the three permissions in the order they are shown by `ls -l`.
+    pub const ALL: [Perm; 3] = [Perm::Read, Perm::Write, Perm::Execute];
+
+    /// returns the bit of the permission inside a class octal digit (`4`, `2` or `1`).
+    pub fn bit(&self) -> u32 {
+        match self {
+            Perm::Read => 0o4,
+            Perm::Write => 0o2,
+            Perm::Execute => 0o1,
+        }
+    }
+    /// returns the name of the permission: `read`, `write` or `execute`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Perm::Read => "read",
+            Perm::Write => "write",
+            Perm::Execute => "execute",
+        }
+    }
+    /// returns the letter of the permission: `r`, `w` or `x`.
+    pub fn symbol(&self) -> char {
+        match self {
+            Perm::Read => 'r',
+            Perm::Write => 'w',
+            Perm::Execute => 'x',
+        }
+    }
+}
+
+impl Display for Perm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+    /// a set of owner classes, written like the `who` part of `chmod`: `u`, `g`, `o`, `a`, `ug`...
+    ///
+    /// # Example
+    /// ```
+    /// use fmodeparser::{Class, WhoSet};
+    ///
+    /// let who: WhoSet = "go".parse().unwrap();
+    /// assert_eq!(who, WhoSet::GROUP | WhoSet::OTHER);
+    /// assert!(who.contains(Class::Group.into()));
+    /// assert_eq!(WhoSet::ALL.to_string(), "ugo");
+    /// ```
+    pub struct WhoSet: u8 {
+        const USER = 0b100;
+        const GROUP = 0b010;
+        const OTHER = 0b001;
+        const ALL = 0b111;
+    }
+}
+
+impl WhoSet {
+    /// returns the classes of the set in the order they are shown by `ls -l`.
+    pub fn classes(&self) -> impl Iterator<Item = Class> + '_ {
+        Class::ALL
+            .into_iter()
+            .filter(|class| self.contains(WhoSet::from(*class)))
+    }
+}
+
+impl From<Class> for WhoSet {
+    fn from(class: Class) -> Self {
+        match class {
+            Class::User => WhoSet::USER,
+            Class::Group => WhoSet::GROUP,
+            Class::Other => WhoSet::OTHER,
+        }
+    }
+}
+
+impl FromStr for WhoSet {
+    type Err = FullPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut who = WhoSet::empty();
+        for (position, symbol) in s.chars().enumerate() {
+            who |= match symbol {
+                'u' => WhoSet::USER,
+                'g' => WhoSet::GROUP,
+                'o' => WhoSet::OTHER,
+                'a' => WhoSet::ALL,
+                _ => {
+                    return Err(FullPermissionError::invalid_symbol(
+                        s,
+                        position,
+                        Some(symbol),
+                        "ugoa",
+                    ))
+                }
+            };
+        }
+        Ok(who)
+    }
+}
+
+impl Display for WhoSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for class in self.classes() {
+            write!(f, "{class}")?;
+        }
+        Ok(())
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+    /// a set of permissions, written like the `perms` part of `chmod`: `r`, `rw`, `rwx`...
+    ///
+    /// the bits are the same as the ones of a class octal digit so `PermSet::from_bits_truncate(6)`
+    /// is `rw`.
+    pub struct PermSet: u32 {
+        const READ = 0o4;
+        const WRITE = 0o2;
+        const EXECUTE = 0o1;
+    }
+}
+
+impl From<Perm> for PermSet {
+    fn from(perm: Perm) -> Self {
+        PermSet::from_bits_truncate(perm.bit())
+    }
+}
+
+impl FromStr for PermSet {
+    type Err = FullPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut perms = PermSet::empty();
+        for (position, symbol) in s.chars().enumerate() {
+            perms |= match symbol {
+                'r' => PermSet::READ,
+                'w' => PermSet::WRITE,
+                'x' => PermSet::EXECUTE,
+                _ => {
+                    return Err(FullPermissionError::invalid_symbol(
+                        s,
+                        position,
+                        Some(symbol),
+                        "rwx",
+                    ))
+                }
+            };
+        }
+        Ok(perms)
+    }
+}
+
+impl Display for PermSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for perm in Perm::ALL {
+            if self.contains(perm.into()) {
+                write!(f, "{perm}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,314 @@
+//! This module contains the wildcard patterns of `ls -l` permission strings, such as
+//! `-rw?r??---` or `d???????w?`, compiled into mask and value pairs of the mode bits.
+//!
+
+use crate::category_util::mode_bits::FILE_TYPE_MASK;
+use crate::{FileType, FullPermission, FullPermissionError};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// the letters of the file type slot.
+const FILE_TYPE_SYMBOLS: &str = "-dlcbpsDwP";
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+/// a pattern of the `ls -l` form of a permission.
+///
+/// every slot of the permission is either a letter, `?` which matches any state of the
+/// slot, or a class of letters between brackets like `[sx]`, a class starting with `!`
+/// matches the letters that are not listed. the file type slot can be left out to match
+/// every file type.
+///
+/// the pattern is compiled into a list of mask and value pairs: a mode matches when, for
+/// one of the pairs, the bits of the mask are the bits of the value.
+///
+/// # Example
+/// ```
+/// use fmodeparser::{FullPermission, PermPattern};
+/// # use fmodeparser::FullPermissionError;
+/// # fn main() -> Result<(), FullPermissionError> {
+///
+/// let other_writable_directory: PermPattern = "d???????w?".parse()?;
+/// assert_eq!(other_writable_directory.get_masks(), [(0o170002, 0o040002)]);
+/// assert!(other_writable_directory.matches("drwxrwxrwt".parse()?));
+/// assert!(!other_writable_directory.matches("-rw-rw-rw-".parse()?));
+///
+/// let pattern: PermPattern = "-rw[sx]r??---".parse()?;
+/// assert!(pattern.matches(FullPermission::new(0o104750)?));
+/// assert!(pattern.matches(FullPermission::new(0o100740)?));
+/// assert!(!pattern.matches(FullPermission::new(0o100640)?));
+///
+/// let any_type: PermPattern = "rw[!x-]??????".parse()?;
+/// assert!(any_type.matches_mode(0o044600));
+/// # Ok(())
+/// # }
+/// ```
+pub struct PermPattern {
+    pattern: String,
+    masks: Vec<(u32, u32)>,
+}
+
+impl PermPattern {
+    /// compiles a pattern, the errors point at the first character that is not valid.
+    pub fn new(pattern: &str) -> Result<Self, FullPermissionError> {
+        let slots = parse_slots(pattern)?;
+        let offset = usize::from(slots.len() == 10);
+        let mut masks = vec![(0, 0)];
+        if offset == 1 {
+            masks = cross(&masks, &file_type_pairs(pattern, &slots[0])?);
+        }
+        for (index, slot) in slots[offset..].iter().enumerate() {
+            let states = slot_states(index);
+            let allowed = slot.allowed(pattern, &states)?;
+            let mask = states.iter().fold(0, |mask, (_, bits)| mask | bits);
+            masks = cross(&masks, &cover(mask, &allowed));
+        }
+        Ok(Self {
+            pattern: pattern.to_string(),
+            masks,
+        })
+    }
+    /// returns the `(mask, value)` pairs of the pattern, a mode matches when
+    /// `mode & mask == value` for one of them.
+    pub fn get_masks(&self) -> &[(u32, u32)] {
+        &self.masks
+    }
+    /// returns `true` if the permission matches the pattern.
+    pub fn matches(&self, permission: FullPermission) -> bool {
+        self.matches_mode(permission.get_mode())
+    }
+    /// returns `true` if the raw mode matches the pattern, a mode without file type bits is
+    /// a regular file.
+    pub fn matches_mode(&self, mode: u32) -> bool {
+        let mode = if mode & FILE_TYPE_MASK == 0 {
+            mode | FileType::Regular.bits()
+        } else {
+            mode
+        };
+        self.masks.iter().any(|&(mask, value)| mode & mask == value)
+    }
+}
+
+#[derive(Debug)]
+/// a slot of a pattern.
+enum Slot {
+    /// `?`
+    Any,
+    /// a letter or a class of letters, the positions of the letters and of the end of the
+    /// class are kept for the errors.
+    Letters {
+        letters: Vec<(usize, char)>,
+        negated: bool,
+        end: (usize, char),
+    },
+}
+
+impl Slot {
+    /// returns the bits of the states the slot allows.
+    fn allowed(
+        &self,
+        pattern: &str,
+        states: &[(char, u32)],
+    ) -> Result<Vec<u32>, FullPermissionError> {
+        let Slot::Letters {
+            letters,
+            negated,
+            end,
+        } = self
+        else {
+            return Ok(states.iter().map(|(_, bits)| *bits).collect());
+        };
+        let symbols: String = states.iter().map(|(symbol, _)| *symbol).collect();
+        for &(position, letter) in letters {
+            if !symbols.contains(letter) {
+                return Err(FullPermissionError::invalid_symbol(
+                    pattern,
+                    position,
+                    Some(letter),
+                    &symbols,
+                ));
+            }
+        }
+        let allowed: Vec<u32> = states
+            .iter()
+            .filter(|(symbol, _)| letters.iter().any(|(_, letter)| letter == symbol) != *negated)
+            .map(|(_, bits)| *bits)
+            .collect();
+        if allowed.is_empty() {
+            // a class that matches nothing, such as `[]` or `[!-r]`
+            return Err(FullPermissionError::invalid_symbol(
+                pattern,
+                end.0,
+                Some(end.1),
+                &symbols,
+            ));
+        }
+        Ok(allowed)
+    }
+}
+
+/// splits a pattern into its slots.
+fn parse_slots(pattern: &str) -> Result<Vec<Slot>, FullPermissionError> {
+    let chars: Vec<(usize, char)> = pattern.chars().enumerate().collect();
+    let mut slots = Vec::new();
+    let mut index = 0;
+    while let Some(&(position, c)) = chars.get(index) {
+        if slots.len() == 10 {
+            return Err(FullPermissionError::invalid_symbol(
+                pattern,
+                position,
+                Some(c),
+                "",
+            ));
+        }
+        index += 1;
+        let slot = match c {
+            '?' => Slot::Any,
+            '[' => {
+                let negated = chars.get(index).is_some_and(|&(_, c)| c == '!');
+                index += usize::from(negated);
+                let start = index;
+                while chars.get(index).is_some_and(|&(_, c)| c != ']') {
+                    index += 1;
+                }
+                let Some(&end) = chars.get(index) else {
+                    return Err(FullPermissionError::invalid_symbol(
+                        pattern, index, None, "]",
+                    ));
+                };
+                index += 1;
+                Slot::Letters {
+                    letters: chars[start..index - 1].to_vec(),
+                    negated,
+                    end,
+                }
+            }
+            c => Slot::Letters {
+                letters: vec![(position, c)],
+                negated: false,
+                end: (position, c),
+            },
+        };
+        slots.push(slot);
+    }
+    if slots.len() < 9 {
+        return Err(FullPermissionError::invalid_symbol(
+            pattern,
+            chars.len(),
+            None,
+            "?[",
+        ));
+    }
+    Ok(slots)
+}
+
+/// returns the letters and the bits of a permission slot, counted after the file type.
+fn slot_states(index: usize) -> Vec<(char, u32)> {
+    let shift = 6 - 3 * (index / 3);
+    match index % 3 {
+        0 => vec![('-', 0), ('r', 0o4 << shift)],
+        1 => vec![('-', 0), ('w', 0o2 << shift)],
+        _ => {
+            let execute = 0o1 << shift;
+            let (special, letter) = [(0o4000, 's'), (0o2000, 's'), (0o1000, 't')][index / 3];
+            vec![
+                ('-', 0),
+                ('x', execute),
+                (letter.to_ascii_uppercase(), special),
+                (letter, execute | special),
+            ]
+        }
+    }
+}
+
+/// returns the pairs of the file type slot.
+fn file_type_pairs(pattern: &str, slot: &Slot) -> Result<Vec<(u32, u32)>, FullPermissionError> {
+    let states: Vec<(char, u32)> = FILE_TYPE_SYMBOLS
+        .chars()
+        .filter_map(|symbol| Some((symbol, FileType::from_symbol(symbol)?.bits())))
+        .collect();
+    if matches!(slot, Slot::Any) {
+        return Ok(vec![(0, 0)]);
+    }
+    let mut pairs: Vec<(u32, u32)> = slot
+        .allowed(pattern, &states)?
+        .into_iter()
+        .map(|bits| (FILE_TYPE_MASK, bits))
+        .collect();
+    // the whiteout and the event port share their bits
+    pairs.dedup();
+    Ok(pairs)
+}
+
+/// returns the largest `(mask, value)` pairs whose modes are all allowed states of the
+/// slot bits, together they match exactly the allowed states.
+fn cover(slot_mask: u32, allowed: &[u32]) -> Vec<(u32, u32)> {
+    let mut pairs = Vec::new();
+    for mask in submasks(slot_mask) {
+        for value in submasks(mask) {
+            if submasks(slot_mask)
+                .filter(|state| state & mask == value)
+                .all(|state| allowed.contains(&state))
+            {
+                pairs.push((mask, value));
+            }
+        }
+    }
+    // a pair is dropped when a pair with fewer bits in its mask matches all its modes
+    let larger = |&(mask, value): &(u32, u32), &(other_mask, other_value): &(u32, u32)| {
+        (mask, value) != (other_mask, other_value)
+            && other_mask & mask == other_mask
+            && value & other_mask == other_value
+    };
+    pairs
+        .iter()
+        .filter(|pair| !pairs.iter().any(|other| larger(pair, other)))
+        .copied()
+        .collect()
+}
+
+/// returns every subset of the bits of `mask`, `mask` itself first.
+fn submasks(mask: u32) -> impl Iterator<Item = u32> {
+    let mut next = Some(mask);
+    std::iter::from_fn(move || {
+        let current = next?;
+        next = (current != 0).then(|| (current - 1) & mask);
+        Some(current)
+    })
+}
+
+/// returns the pairs matching a mode of `left` and a mode of `right`.
+fn cross(left: &[(u32, u32)], right: &[(u32, u32)]) -> Vec<(u32, u32)> {
+    let mut pairs = Vec::with_capacity(left.len() * right.len());
+    for &(left_mask, left_value) in left {
+        for &(right_mask, right_value) in right {
+            let pair = (left_mask | right_mask, left_value | right_value);
+            if !pairs.contains(&pair) {
+                pairs.push(pair);
+            }
+        }
+    }
+    pairs
+}
+
+impl FromStr for PermPattern {
+    type Err = FullPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PermPattern::new(s)
+    }
+}
+
+impl TryFrom<&str> for PermPattern {
+    type Error = FullPermissionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        PermPattern::new(value)
+    }
+}
+
+/// writes the pattern as it was compiled.
+impl Display for PermPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.pattern)
+    }
+}
@@ -0,0 +1,137 @@
+//! This module contains the bitwise operators and the set algebra of
+//! [`FullPermission`](crate::FullPermission).
+//!
+//! every operation only works on the permission and special bits, the file type of the
+//! left hand side is always kept. the right hand side can be another `FullPermission`,
+//! [`ModeBits`](crate::ModeBits) or a raw `u32` mode.
+//!
+
+use crate::{FullPermission, ModeBits};
+use std::cmp::Ordering;
+use std::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Sub, SubAssign,
+};
+
+impl From<FullPermission> for ModeBits {
+    fn from(permission: FullPermission) -> Self {
+        permission.get_mode_bits()
+    }
+}
+
+impl From<&FullPermission> for ModeBits {
+    fn from(permission: &FullPermission) -> Self {
+        permission.get_mode_bits()
+    }
+}
+
+impl FullPermission {
+    /// returns `true` if every permission bit of `self` is also set in `other`, in other
+    /// words `self` is not more permissive than `other`.
+    ///
+    /// # Example
+    /// ```
+    /// use fmodeparser::FullPermission;
+    /// # use fmodeparser::FullPermissionError;
+    /// # fn main() -> Result<(), FullPermissionError> {
+    ///
+    /// let permission = FullPermission::new(0o100640)?;
+    /// assert!(permission.is_subset_of(0o640));
+    /// assert!(!permission.is_subset_of(0o600));
+    /// assert_eq!((permission & !0o022).mode_as_octal(), "100640");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_subset_of(&self, other: impl Into<ModeBits>) -> bool {
+        other.into().contains(self.get_mode_bits())
+    }
+    /// returns `true` if every permission bit of `other` is also set in `self`.
+    pub fn is_superset_of(&self, other: impl Into<ModeBits>) -> bool {
+        self.get_mode_bits().contains(other.into())
+    }
+    /// returns the bits set in both `self` and `other`.
+    pub fn intersection(&self, other: impl Into<ModeBits>) -> FullPermission {
+        *self & other
+    }
+    /// returns the bits set in either `self` or `other`.
+    pub fn union(&self, other: impl Into<ModeBits>) -> FullPermission {
+        *self | other
+    }
+    /// compares the permission bits by inclusion, this is a partial order: two permissions
+    /// where each one has a bit the other lacks are not comparable and `None` is returned.
+    ///
+    /// the [`Ord`] implementation compares the mode numbers instead, it is a total order
+    /// used for sorting.
+    pub fn subset_cmp(&self, other: impl Into<ModeBits>) -> Option<Ordering> {
+        let (mine, theirs) = (self.get_mode_bits(), other.into());
+        match (theirs.contains(mine), mine.contains(theirs)) {
+            (true, true) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Less),
+            (false, true) => Some(Ordering::Greater),
+            (false, false) => None,
+        }
+    }
+}
+
+impl<T: Into<ModeBits>> BitAnd<T> for FullPermission {
+    type Output = FullPermission;
+
+    fn bitand(self, rhs: T) -> Self::Output {
+        self.with_mode_bits(self.get_mode_bits() & rhs.into())
+    }
+}
+
+impl<T: Into<ModeBits>> BitOr<T> for FullPermission {
+    type Output = FullPermission;
+
+    fn bitor(self, rhs: T) -> Self::Output {
+        self.with_mode_bits(self.get_mode_bits() | rhs.into())
+    }
+}
+
+impl<T: Into<ModeBits>> BitXor<T> for FullPermission {
+    type Output = FullPermission;
+
+    fn bitxor(self, rhs: T) -> Self::Output {
+        self.with_mode_bits(self.get_mode_bits() ^ rhs.into())
+    }
+}
+
+impl<T: Into<ModeBits>> Sub<T> for FullPermission {
+    type Output = FullPermission;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        self.with_mode_bits(self.get_mode_bits() - rhs.into())
+    }
+}
+
+impl Not for FullPermission {
+    type Output = FullPermission;
+
+    fn not(self) -> Self::Output {
+        self.with_mode_bits(!self.get_mode_bits())
+    }
+}
+
+impl<T: Into<ModeBits>> BitAndAssign<T> for FullPermission {
+    fn bitand_assign(&mut self, rhs: T) {
+        *self = *self & rhs;
+    }
+}
+
+impl<T: Into<ModeBits>> BitOrAssign<T> for FullPermission {
+    fn bitor_assign(&mut self, rhs: T) {
+        *self = *self | rhs;
+    }
+}
+
+impl<T: Into<ModeBits>> BitXorAssign<T> for FullPermission {
+    fn bitxor_assign(&mut self, rhs: T) {
+        *self = *self ^ rhs;
+    }
+}
+
+impl<T: Into<ModeBits>> SubAssign<T> for FullPermission {
+    fn sub_assign(&mut self, rhs: T) {
+        *self = *self - rhs;
+    }
+}
@@ -0,0 +1,350 @@
+//! This module contains the snapshot of the modes and owners of a directory tree, its
+//! diffable text format, its restore and the diff of two snapshots, like
+//! `getfacl -R` and `setfacl --restore` do for ACLs.
+//!
+//! the text format starts with a header line, then every entry is a line with the mode in
+//! octal, the uid, the gid and the escaped path relative to the root (`.` for the root
+//! itself). the entries are sorted by path so two snapshots can be compared with `diff`:
+//!
+//! ```text
+//! # fmodeparser snapshot
+//! 040755 1000 1000 .
+//! 040750 1000 1000 bin
+//! 100755 1000 1000 bin/run.sh
+//! ```
+//!
+
+use crate::category_util::path_escape::{escape_path, unescape_path};
+use crate::{FileType, FullPermission, FullPermissionError, ModeBits, PermStrParser};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// the first line of every snapshot.
+const SNAPSHOT_HEADER: &str = "# fmodeparser snapshot";
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+/// the mode and owner of a single file of a snapshot.
+pub struct SnapshotEntry {
+    path: PathBuf,
+    permission: FullPermission,
+    uid: u32,
+    gid: u32,
+}
+
+impl SnapshotEntry {
+    /// returns the path relative to the root of the snapshot, `.` for the root itself.
+    pub fn get_path(&self) -> &Path {
+        &self.path
+    }
+    /// returns the file type and mode of the file.
+    pub fn get_permission(&self) -> FullPermission {
+        self.permission
+    }
+    /// returns the user id of the owner.
+    pub fn get_uid(&self) -> u32 {
+        self.uid
+    }
+    /// returns the group id of the owner.
+    pub fn get_gid(&self) -> u32 {
+        self.gid
+    }
+}
+
+/// writes the entry as a line of the snapshot text format.
+impl Display for SnapshotEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.permission.mode_as_octal(),
+            self.uid,
+            self.gid,
+            escape_path(&self.path)
+        )
+    }
+}
+
+/// parses a line of the snapshot text format.
+impl FromStr for SnapshotEntry {
+    type Err = FullPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| FullPermissionError::InvalidLine {
+            line: 1,
+            input: s.to_string(),
+            reason: reason.to_string(),
+        };
+        let mut fields = s.splitn(4, ' ');
+        let (Some(mode), Some(uid), Some(gid), Some(path)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return Err(invalid("expected a mode, a uid, a gid and a path"));
+        };
+        let mode = u32::from_str_radix(mode, 8).map_err(|_| invalid("the mode is not octal"))?;
+        Ok(Self {
+            permission: FullPermission::new(mode)?,
+            uid: uid
+                .parse()
+                .map_err(|_| invalid("the uid is not a number"))?,
+            gid: gid
+                .parse()
+                .map_err(|_| invalid("the gid is not a number"))?,
+            path: unescape_path(path).ok_or_else(|| invalid("the path has an invalid escape"))?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+/// a difference between two snapshots.
+pub enum SnapshotDiff {
+    /// the entry only exists in the newer snapshot.
+    Added(SnapshotEntry),
+    /// the entry only exists in the older snapshot.
+    Removed(SnapshotEntry),
+    /// the mode or the owner of the entry changed.
+    Changed {
+        before: SnapshotEntry,
+        after: SnapshotEntry,
+    },
+}
+
+/// writes the difference like a line of a unified diff: `+` added, `-` removed and `~`
+/// changed with the old and the new values.
+impl Display for SnapshotDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotDiff::Added(entry) => write!(f, "+ {entry}"),
+            SnapshotDiff::Removed(entry) => write!(f, "- {entry}"),
+            SnapshotDiff::Changed { before, after } => write!(
+                f,
+                "~ {}: {} {}:{} -> {} {}:{}",
+                escape_path(&after.path),
+                before.permission,
+                before.uid,
+                before.gid,
+                after.permission,
+                after.uid,
+                after.gid
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+/// the modes and owners of every entry under a root, symbolic links are recorded but
+/// never followed.
+///
+/// # Example
+/// ```
+/// use fmodeparser::{FullPermission, PermissionSnapshot, SnapshotDiff};
+/// # use fmodeparser::FullPermissionError;
+/// # fn main() -> Result<(), FullPermissionError> {
+/// # let directory = tempfile::tempdir()?;
+/// # let root = directory.path();
+/// # std::fs::write(root.join("config"), "")?;
+/// # FullPermission::new(0o40755)?.apply_to(root)?;
+/// # FullPermission::new(0o100644)?.apply_to(root.join("config"))?;
+///
+/// let snapshot = PermissionSnapshot::capture(root)?;
+/// FullPermission::new(0o100600)?.apply_to(root.join("config"))?;
+///
+/// let changes = snapshot.diff(&PermissionSnapshot::capture(root)?);
+/// assert_eq!(changes.len(), 1);
+/// assert!(matches!(&changes[0], SnapshotDiff::Changed { after, .. }
+///     if after.get_permission().to_string() == "-rw-------"));
+///
+/// snapshot.restore(root)?;
+/// assert!(snapshot.diff(&PermissionSnapshot::capture(root)?).is_empty());
+///
+/// let text = snapshot.to_string();
+/// assert_eq!(text.parse::<PermissionSnapshot>()?, snapshot);
+/// # Ok(())
+/// # }
+/// ```
+pub struct PermissionSnapshot {
+    entries: BTreeMap<PathBuf, SnapshotEntry>,
+}
+
+impl PermissionSnapshot {
+    /// records the mode and owner of the root and of every entry under it.
+    pub fn capture(root: impl AsRef<Path>) -> Result<Self, FullPermissionError> {
+        let root = root.as_ref();
+        let mut snapshot = Self::default();
+        snapshot.capture_entry(root, Path::new("."))?;
+        Ok(snapshot)
+    }
+    fn capture_entry(&mut self, path: &Path, relative: &Path) -> Result<(), FullPermissionError> {
+        let metadata = path
+            .symlink_metadata()
+            .map_err(|error| FullPermissionError::io(path, error))?;
+        let entry = SnapshotEntry {
+            path: relative.to_path_buf(),
+            permission: metadata.get_full_permission()?,
+            uid: metadata.uid(),
+            gid: metadata.gid(),
+        };
+        self.entries.insert(entry.path.clone(), entry);
+        if !metadata.is_dir() {
+            return Ok(());
+        }
+        let children =
+            std::fs::read_dir(path).map_err(|error| FullPermissionError::io(path, error))?;
+        for child in children {
+            let name = child
+                .map_err(|error| FullPermissionError::io(path, error))?
+                .file_name();
+            let child_relative = if relative == Path::new(".") {
+                PathBuf::from(&name)
+            } else {
+                relative.join(&name)
+            };
+            self.capture_entry(&path.join(&name), &child_relative)?;
+        }
+        Ok(())
+    }
+    /// returns the entries sorted by path.
+    pub fn get_entries(&self) -> impl Iterator<Item = &SnapshotEntry> + '_ {
+        self.entries.values()
+    }
+    /// returns the entry of a path relative to the root.
+    pub fn get(&self, path: impl AsRef<Path>) -> Option<&SnapshotEntry> {
+        self.entries.get(path.as_ref())
+    }
+    /// gives back every entry under `root` its recorded owner and mode, the owner is only
+    /// changed when it differs since that needs privileges.
+    ///
+    /// the mode of symbolic links is not restored, and every entry is tried before the
+    /// first error is returned.
+    ///
+    /// like [`RecursiveChmod`](crate::RecursiveChmod) a directory is restored before its
+    /// content when its recorded mode lets its owner read and enter it, otherwise it is
+    /// restored after its content.
+    pub fn restore(&self, root: impl AsRef<Path>) -> Result<(), FullPermissionError> {
+        let root = root.as_ref();
+        let walkable = ModeBits::USER_READ | ModeBits::USER_EXECUTE;
+        let mut failures = Vec::new();
+        let mut restore = |entry: &SnapshotEntry| {
+            if let Err(failure) = restore_entry(root, entry) {
+                failures.push(failure);
+            }
+        };
+        // the entries are sorted so a directory comes right before its content, the
+        // directories waiting for the end of their content are stacked
+        let mut waiting: Vec<&SnapshotEntry> = Vec::new();
+        for entry in self.entries.values() {
+            while let Some(directory) = waiting.pop() {
+                if contains(&directory.path, &entry.path) {
+                    waiting.push(directory);
+                    break;
+                }
+                restore(directory);
+            }
+            let permission = entry.permission;
+            if permission.get_file_type() == FileType::Directory
+                && !permission.get_mode_bits().contains(walkable)
+            {
+                waiting.push(entry);
+            } else {
+                restore(entry);
+            }
+        }
+        waiting.into_iter().rev().for_each(&mut restore);
+        match failures.into_iter().next() {
+            Some(failure) => Err(failure),
+            None => Ok(()),
+        }
+    }
+    /// returns the differences from `self` to the newer snapshot `other`, sorted by path.
+    pub fn diff(&self, other: &PermissionSnapshot) -> Vec<SnapshotDiff> {
+        let mut paths: Vec<&PathBuf> = self.entries.keys().chain(other.entries.keys()).collect();
+        paths.sort();
+        paths.dedup();
+        paths
+            .into_iter()
+            .filter_map(
+                |path| match (self.entries.get(path), other.entries.get(path)) {
+                    (Some(before), Some(after)) if before != after => Some(SnapshotDiff::Changed {
+                        before: before.clone(),
+                        after: after.clone(),
+                    }),
+                    (Some(before), None) => Some(SnapshotDiff::Removed(before.clone())),
+                    (None, Some(after)) => Some(SnapshotDiff::Added(after.clone())),
+                    _ => None,
+                },
+            )
+            .collect()
+    }
+}
+
+/// returns `true` if `path` is inside the directory, every path is inside the root `.`.
+fn contains(directory: &Path, path: &Path) -> bool {
+    directory == Path::new(".") || path.starts_with(directory)
+}
+
+/// restores the owner then the mode, changing the owner clears the setuid and setgid bits.
+///
+/// the mode of a symbolic link is never restored, it cannot be changed on most systems and
+/// asking for it without following the link fails on older C libraries even for the other
+/// files, so the plain `chmod` is used and the links are skipped before it follows them.
+fn restore_entry(root: &Path, entry: &SnapshotEntry) -> Result<(), FullPermissionError> {
+    let path = root.join(&entry.path);
+    let metadata = path
+        .symlink_metadata()
+        .map_err(|error| FullPermissionError::io(&path, error))?;
+    if metadata.uid() != entry.uid || metadata.gid() != entry.gid {
+        std::os::unix::fs::lchown(&path, Some(entry.uid), Some(entry.gid))
+            .map_err(|error| FullPermissionError::io(&path, error))?;
+    }
+    if entry.permission.get_file_type() == FileType::Symlink || metadata.file_type().is_symlink() {
+        return Ok(());
+    }
+    entry.permission.apply_to(&path)
+}
+
+/// writes the snapshot text format.
+impl Display for PermissionSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{SNAPSHOT_HEADER}")?;
+        for entry in self.entries.values() {
+            writeln!(f, "{entry}")?;
+        }
+        Ok(())
+    }
+}
+
+/// parses the snapshot text format, the errors name the line that is not valid.
+impl FromStr for PermissionSnapshot {
+    type Err = FullPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate();
+        match lines.next() {
+            Some((_, SNAPSHOT_HEADER)) => {}
+            first => {
+                return Err(FullPermissionError::InvalidLine {
+                    line: 1,
+                    input: first.map_or("", |(_, line)| line).to_string(),
+                    reason: format!("a snapshot must start with `{SNAPSHOT_HEADER}`"),
+                })
+            }
+        }
+        let mut snapshot = Self::default();
+        for (index, line) in lines {
+            let entry: SnapshotEntry = line.parse().map_err(|error| match error {
+                FullPermissionError::InvalidLine { input, reason, .. } => {
+                    FullPermissionError::InvalidLine {
+                        line: index + 1,
+                        input,
+                        reason,
+                    }
+                }
+                error => error,
+            })?;
+            snapshot.entries.insert(entry.path.clone(), entry);
+        }
+        Ok(snapshot)
+    }
+}
@@ -0,0 +1,495 @@
+//! This module contains the recursive `chmod` walker that applies modes or symbolic
+//! expressions to a whole tree, with distinct rules for directories and the other files.
+//!
+
+use crate::{FileType, FullPermission, FullPermissionError, ModeBits, SymbolicMode};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::fmt::{Display, Formatter};
+use std::fs::Metadata;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// the change applied to a file by the walker.
+pub enum ModeRule {
+    /// replaces the permission and special bits, like `chmod 644`.
+    ///
+    /// like GNU `chmod` the setuid and setgid bits of a directory are kept unless the mode
+    /// sets them, so `chmod -R 750` does not drop the setgid bit of shared directories.
+    Mode(ModeBits),
+    /// replaces every permission and special bit, like `chmod 00750` whose five digits also
+    /// clear the setuid and setgid bits of directories.
+    FullMode(ModeBits),
+    /// applies a symbolic expression, like `chmod u+rwX,go=rX`.
+    Expression(SymbolicMode),
+}
+
+impl ModeRule {
+    /// returns the mode of a file after the rule is applied, the file type bits are kept.
+    ///
+    /// `umask` is only used by the clauses of an expression that have no `who` part.
+    pub fn apply_to_mode(&self, mode: u32, umask: u32) -> u32 {
+        match self {
+            ModeRule::Mode(bits) => {
+                let kept = if FileType::from_bits(mode) == FileType::Directory {
+                    mode & (ModeBits::SETUID | ModeBits::SETGID).bits()
+                } else {
+                    0
+                };
+                (mode & !ModeBits::all().bits()) | kept | bits.bits()
+            }
+            ModeRule::FullMode(bits) => (mode & !ModeBits::all().bits()) | bits.bits(),
+            ModeRule::Expression(expression) => expression.apply_to_mode(mode, umask),
+        }
+    }
+}
+
+impl From<ModeBits> for ModeRule {
+    fn from(bits: ModeBits) -> Self {
+        ModeRule::Mode(bits)
+    }
+}
+
+impl From<FullPermission> for ModeRule {
+    fn from(permission: FullPermission) -> Self {
+        ModeRule::Mode(permission.get_mode_bits())
+    }
+}
+
+impl From<SymbolicMode> for ModeRule {
+    fn from(expression: SymbolicMode) -> Self {
+        ModeRule::Expression(expression)
+    }
+}
+
+/// accepts an octal mode like `chmod` does or a symbolic expression, a mode of 5 digits or
+/// more is a [`FullMode`](ModeRule::FullMode).
+impl FromStr for ModeRule {
+    type Err = FullPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit()) {
+            return Ok(ModeRule::Expression(s.parse()?));
+        }
+        let value =
+            u32::from_str_radix(s, 8).map_err(|source| FullPermissionError::InvalidNumber {
+                input: s.to_string(),
+                radix: 8,
+                source,
+            })?;
+        if value > ModeBits::all().bits() {
+            return Err(FullPermissionError::ModeOutOfRange {
+                kind: "chmod mode",
+                value,
+                allowed: ModeBits::all().bits(),
+            });
+        }
+        if s.len() >= 5 {
+            return Ok(ModeRule::FullMode(ModeBits::from(value)));
+        }
+        Ok(ModeRule::Mode(ModeBits::from(value)))
+    }
+}
+
+impl TryFrom<&str> for ModeRule {
+    type Error = FullPermissionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for ModeRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModeRule::Mode(bits) => write!(f, "{:04o}", bits.bits()),
+            ModeRule::FullMode(bits) => write!(f, "{:05o}", bits.bits()),
+            ModeRule::Expression(expression) => write!(f, "{expression}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+/// what the walker does with the symbolic links it finds.
+pub enum SymlinkPolicy {
+    /// leaves the links and what they point to untouched, like `chmod -R` does.
+    #[default]
+    Skip,
+    /// changes the file the link points to and walks into linked directories, every
+    /// directory is only visited once so link loops end.
+    Follow,
+    /// reports every link as a [`Policy`](FullPermissionError::Policy) error.
+    Error,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+/// a file changed by the walker, or that would be changed in a dry run.
+pub struct ChmodChange {
+    path: PathBuf,
+    before: FullPermission,
+    after: FullPermission,
+}
+
+impl ChmodChange {
+    /// returns the path of the file.
+    pub fn get_path(&self) -> &Path {
+        &self.path
+    }
+    /// returns the permission before the change.
+    pub fn get_before(&self) -> FullPermission {
+        self.before
+    }
+    /// returns the permission after the change.
+    pub fn get_after(&self) -> FullPermission {
+        self.after
+    }
+}
+
+#[derive(Debug, Default)]
+/// the result of a [`RecursiveChmod`] run: the changed files and the errors that were
+/// skipped when continuing on errors.
+pub struct ChmodReport {
+    changes: Vec<ChmodChange>,
+    errors: Vec<FullPermissionError>,
+}
+
+impl ChmodReport {
+    /// returns the files whose mode was changed in the order they were visited, the files
+    /// that already had the wanted mode are not part of it.
+    pub fn get_changes(&self) -> &[ChmodChange] {
+        &self.changes
+    }
+    /// returns the errors that were met, only when continuing on errors.
+    pub fn get_errors(&self) -> &[FullPermissionError] {
+        &self.errors
+    }
+    /// returns `true` if no error was met.
+    pub fn is_success(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+/// applies permission changes to a whole tree, the replacement of
+/// `find -type d -exec chmod ...` and `find -type f -exec chmod ...`.
+///
+/// directories use the directory rule and every other file uses the file rule, a file
+/// without a rule is left untouched. the root itself is changed as well.
+///
+/// the globs match the path relative to the root: a file must match one of the included
+/// globs when there are some, and an excluded file or directory is skipped with everything
+/// under it.
+///
+/// a directory is changed before its content when the new mode lets its owner read and
+/// enter it, otherwise it is changed after its content.
+///
+/// # Example
+/// ```
+/// use fmodeparser::{ModeRule, PermStrParser, RecursiveChmod};
+/// # use fmodeparser::FullPermissionError;
+/// # fn main() -> Result<(), FullPermissionError> {
+/// # let directory = tempfile::tempdir()?;
+/// # let root = directory.path();
+/// # std::fs::create_dir(root.join("bin"))?;
+/// # std::fs::write(root.join("bin/run.sh"), "")?;
+/// # std::fs::write(root.join("README"), "")?;
+///
+/// let report = RecursiveChmod::new(root)
+///     .directory_rule("755".parse::<ModeRule>()?)
+///     .file_rule("u=rw,go=r".parse::<ModeRule>()?)
+///     .exclude("bin/*.sh")?
+///     .run()?;
+/// assert!(report.is_success());
+/// assert_eq!(root.join("bin").convert_permission_to_string()?, "drwxr-xr-x");
+/// assert_eq!(root.join("README").convert_permission_to_string()?, "-rw-r--r--");
+/// # Ok(())
+/// # }
+/// ```
+pub struct RecursiveChmod {
+    root: PathBuf,
+    file_rule: Option<ModeRule>,
+    directory_rule: Option<ModeRule>,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    symlinks: SymlinkPolicy,
+    dry_run: bool,
+    continue_on_error: bool,
+    umask: u32,
+}
+
+/// the state carried while walking the tree.
+struct Walk {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+    visited: HashSet<(u64, u64)>,
+    report: ChmodReport,
+}
+
+impl RecursiveChmod {
+    /// returns a walker of the tree under `root` without any rule.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            file_rule: None,
+            directory_rule: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            symlinks: SymlinkPolicy::default(),
+            dry_run: false,
+            continue_on_error: false,
+            umask: 0,
+        }
+    }
+    /// sets the rule applied to every file that is not a directory.
+    pub fn file_rule(mut self, rule: impl Into<ModeRule>) -> Self {
+        self.file_rule = Some(rule.into());
+        self
+    }
+    /// sets the rule applied to every directory.
+    pub fn directory_rule(mut self, rule: impl Into<ModeRule>) -> Self {
+        self.directory_rule = Some(rule.into());
+        self
+    }
+    /// sets the same rule for the directories and the other files, like `chmod -R`.
+    ///
+    /// an expression with `X` gives the execute permission to the directories only.
+    pub fn rule(self, rule: impl Into<ModeRule>) -> Self {
+        let rule = rule.into();
+        self.file_rule(rule.clone()).directory_rule(rule)
+    }
+    /// only changes the files whose relative path matches the glob or one of the other
+    /// included globs, the directories are still walked.
+    pub fn include(mut self, pattern: &str) -> Result<Self, FullPermissionError> {
+        compile_glob(pattern)?;
+        self.include.push(pattern.to_string());
+        Ok(self)
+    }
+    /// skips the files and directories whose relative path matches the glob.
+    pub fn exclude(mut self, pattern: &str) -> Result<Self, FullPermissionError> {
+        compile_glob(pattern)?;
+        self.exclude.push(pattern.to_string());
+        Ok(self)
+    }
+    /// sets what is done with symbolic links, see [`SymlinkPolicy`].
+    pub fn symlinks(mut self, policy: SymlinkPolicy) -> Self {
+        self.symlinks = policy;
+        self
+    }
+    /// when `true` nothing is changed but the report lists what would change.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+    /// when `true` the errors are collected in the report and the walk goes on, otherwise
+    /// the first error stops the walk and is returned.
+    pub fn continue_on_error(mut self, continue_on_error: bool) -> Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+    /// sets the umask used by the expression clauses without a `who` part like `+x`,
+    /// it is `0` by default.
+    pub fn umask(mut self, umask: u32) -> Self {
+        self.umask = umask & ModeBits::all().bits();
+        self
+    }
+    /// walks the tree and applies the rules.
+    pub fn run(&self) -> Result<ChmodReport, FullPermissionError> {
+        self.run_with(|_, _| Ok(()))
+    }
+    /// walks the tree like [`run`](RecursiveChmod::run) and calls `before_change` with the
+    /// path and its current permission right before every change, an error returned by it
+    /// is handled like a failed change.
+    pub fn run_with<F>(&self, mut before_change: F) -> Result<ChmodReport, FullPermissionError>
+    where
+        F: FnMut(&Path, FullPermission) -> Result<(), FullPermissionError>,
+    {
+        let include = if self.include.is_empty() {
+            None
+        } else {
+            Some(compile_glob_set(&self.include)?)
+        };
+        let mut walk = Walk {
+            include,
+            exclude: compile_glob_set(&self.exclude)?,
+            visited: HashSet::new(),
+            report: ChmodReport::default(),
+        };
+        self.visit(&self.root, Path::new(""), &mut walk, &mut before_change)?;
+        Ok(walk.report)
+    }
+    /// collects the error or returns it when the walk must stop.
+    fn fail(&self, walk: &mut Walk, error: FullPermissionError) -> Result<(), FullPermissionError> {
+        if self.continue_on_error {
+            walk.report.errors.push(error);
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+    /// reads the metadata of a path according to the symlink policy, `None` means the path
+    /// must be skipped.
+    fn metadata(
+        &self,
+        path: &Path,
+        walk: &mut Walk,
+    ) -> Result<Option<Metadata>, FullPermissionError> {
+        let metadata = match path.symlink_metadata() {
+            Ok(metadata) => metadata,
+            Err(error) => {
+                return self
+                    .fail(walk, FullPermissionError::io(path, error))
+                    .map(|_| None)
+            }
+        };
+        if !metadata.file_type().is_symlink() {
+            return Ok(Some(metadata));
+        }
+        match self.symlinks {
+            SymlinkPolicy::Skip => Ok(None),
+            SymlinkPolicy::Error => {
+                let error = FullPermissionError::policy(
+                    path,
+                    "symbolic links are refused by the symlink policy",
+                );
+                self.fail(walk, error).map(|_| None)
+            }
+            SymlinkPolicy::Follow => match path.metadata() {
+                Ok(metadata) => Ok(Some(metadata)),
+                Err(error) => self
+                    .fail(walk, FullPermissionError::io(path, error))
+                    .map(|_| None),
+            },
+        }
+    }
+    fn visit<F>(
+        &self,
+        path: &Path,
+        relative: &Path,
+        walk: &mut Walk,
+        before_change: &mut F,
+    ) -> Result<(), FullPermissionError>
+    where
+        F: FnMut(&Path, FullPermission) -> Result<(), FullPermissionError>,
+    {
+        let is_root = relative.as_os_str().is_empty();
+        if !is_root && walk.exclude.is_match(relative) {
+            return Ok(());
+        }
+        let Some(metadata) = self.metadata(path, walk)? else {
+            return Ok(());
+        };
+        let is_dir = metadata.is_dir();
+        if is_dir && !walk.visited.insert((metadata.dev(), metadata.ino())) {
+            return Ok(());
+        }
+        let included = match &walk.include {
+            None => true,
+            Some(include) => !is_root && include.is_match(relative),
+        };
+        let rule = if is_dir {
+            &self.directory_rule
+        } else {
+            &self.file_rule
+        };
+        let change = match rule {
+            Some(rule) if included => {
+                let before = FullPermission::new(metadata.mode())?;
+                let after = FullPermission::new(rule.apply_to_mode(metadata.mode(), self.umask))?;
+                (before != after).then_some((before, after))
+            }
+            _ => None,
+        };
+        if !is_dir {
+            if let Some((before, after)) = change {
+                self.change(path, before, after, walk, before_change)?;
+            }
+            return Ok(());
+        }
+        // the owner must still be able to read and enter the directory to walk it first
+        let walkable = ModeBits::USER_READ | ModeBits::USER_EXECUTE;
+        let change_first =
+            change.is_some_and(|(_, after)| after.get_mode_bits().contains(walkable));
+        if change_first {
+            if let Some((before, after)) = change {
+                self.change(path, before, after, walk, before_change)?;
+            }
+        }
+        match read_sorted_names(path) {
+            Ok(names) => {
+                for name in names {
+                    self.visit(
+                        &path.join(&name),
+                        &relative.join(&name),
+                        walk,
+                        before_change,
+                    )?;
+                }
+            }
+            Err(error) => self.fail(walk, FullPermissionError::io(path, error))?,
+        }
+        if !change_first {
+            if let Some((before, after)) = change {
+                self.change(path, before, after, walk, before_change)?;
+            }
+        }
+        Ok(())
+    }
+    /// applies or records a single change.
+    fn change<F>(
+        &self,
+        path: &Path,
+        before: FullPermission,
+        after: FullPermission,
+        walk: &mut Walk,
+        before_change: &mut F,
+    ) -> Result<(), FullPermissionError>
+    where
+        F: FnMut(&Path, FullPermission) -> Result<(), FullPermissionError>,
+    {
+        if !self.dry_run {
+            let applied = before_change(path, before).and_then(|_| after.apply_to(path));
+            if let Err(error) = applied {
+                return self.fail(walk, error);
+            }
+        }
+        walk.report.changes.push(ChmodChange {
+            path: path.to_path_buf(),
+            before,
+            after,
+        });
+        Ok(())
+    }
+}
+
+/// returns the names of the entries of a directory sorted so the walk order is stable.
+fn read_sorted_names(path: &Path) -> std::io::Result<Vec<OsString>> {
+    let mut names = std::fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.file_name()))
+        .collect::<Result<Vec<_>, _>>()?;
+    names.sort();
+    Ok(names)
+}
+
+/// compiles a single glob, the `*` wildcard also matches the `/` separators.
+fn compile_glob(pattern: &str) -> Result<Glob, FullPermissionError> {
+    Glob::new(pattern).map_err(|source| FullPermissionError::InvalidGlob {
+        pattern: pattern.to_string(),
+        source,
+    })
+}
+
+fn compile_glob_set(patterns: &[String]) -> Result<GlobSet, FullPermissionError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(compile_glob(pattern)?);
+    }
+    builder
+        .build()
+        .map_err(|source| FullPermissionError::InvalidGlob {
+            pattern: patterns.join(","),
+            source,
+        })
+}
@@ -0,0 +1,379 @@
+//! This module contains the renderer of the GNU `stat --format` directives that describe
+//! the permission and the identity of a file.
+//!
+//! the output is the one of GNU `stat` in the C locale, `stat -c` only adds a newline at
+//! the end.
+//!
+
+use crate::category_util::accounts::{group_name, user_name};
+use crate::category_util::device::device_numbers;
+use crate::{FileType, FullPermissionError, PermStrParser};
+use std::fmt::{Display, Formatter};
+use std::fs::Metadata;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// the directives known by the renderer.
+const DIRECTIVES: &str = "aAfFuUgGtThinN";
+/// the `printf` flags `stat` accepts before the width, the ones a directive does not use
+/// are ignored.
+const FLAGS: &str = "'-+ #0I";
+/// the largest width and precision, the limit of `printf`.
+const MAX_WIDTH: usize = i32::MAX as usize;
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+/// the flags, the width and the precision of a directive, such as `%-8U` or `%#05a`.
+struct Spec {
+    left: bool,
+    zero: bool,
+    alternate: bool,
+    width: usize,
+    precision: Option<usize>,
+}
+
+impl Spec {
+    /// formats a string like the `%s` of `printf`, the precision keeps the first bytes.
+    fn string(&self, text: &str) -> String {
+        let text = match self.precision {
+            Some(precision) if precision < text.len() => {
+                let end = (0..=precision)
+                    .rev()
+                    .find(|&end| text.is_char_boundary(end))
+                    .unwrap_or(0);
+                &text[..end]
+            }
+            _ => text,
+        };
+        self.pad("", text, false)
+    }
+    /// formats a number like the `%u`, `%o` and `%x` of `printf`.
+    fn unsigned(&self, value: u64, radix: u32) -> String {
+        let mut digits = match (radix, self.precision, value) {
+            (_, Some(0), 0) => String::new(),
+            (8, ..) => format!("{value:o}"),
+            (16, ..) => format!("{value:x}"),
+            _ => value.to_string(),
+        };
+        if let Some(precision) = self.precision {
+            digits = format!("{digits:0>precision$}");
+        }
+        let mut prefix = "";
+        if self.alternate && radix == 8 && !digits.starts_with('0') {
+            digits.insert(0, '0');
+        }
+        if self.alternate && radix == 16 && value != 0 {
+            prefix = "0x";
+        }
+        // the `0` flag is ignored with a precision, like `printf` does
+        self.pad(prefix, &digits, self.zero && self.precision.is_none())
+    }
+    fn pad(&self, prefix: &str, text: &str, zero: bool) -> String {
+        let length = prefix.len() + text.len();
+        let padding = self.width.saturating_sub(length);
+        if self.left {
+            format!("{prefix}{text}{}", " ".repeat(padding))
+        } else if zero {
+            format!("{prefix}{}{text}", "0".repeat(padding))
+        } else {
+            format!("{}{prefix}{text}", " ".repeat(padding))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// a piece of a format, a plain text or a directive.
+enum Piece {
+    Text(String),
+    Directive(Spec, char),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// a `stat --format` string that renders the permission and the identity of files.
+///
+/// the directives are:
+///
+/// | directive | value |
+/// |-----------|-------|
+/// | `%a` | the permission bits in octal: `644` |
+/// | `%A` | the permission like `ls -l`: `-rw-r--r--` |
+/// | `%f` | the raw mode in hexadecimal: `81a4` |
+/// | `%F` | the file type: `regular file`, `directory`, `symbolic link`... |
+/// | `%u` `%U` | the user id and name of the owner |
+/// | `%g` `%G` | the group id and name of the owner |
+/// | `%t` `%T` | the major and minor device numbers in hexadecimal |
+/// | `%h` | the number of hard links |
+/// | `%i` | the inode number |
+/// | `%n` | the file name |
+/// | `%N` | the quoted file name, followed by ` -> ` and the target of a symbolic link |
+/// | `%%` | a `%` |
+///
+/// a directive can have the `printf` flags `-`, `0` and `#`, a width and a precision, such
+/// as `%-8U` or `%#05a`. like GNU `stat` the names of `%N` are only quoted when the format
+/// contains a plain `%N`, a `%-20N` alone writes them as they are.
+///
+/// # Example
+/// ```
+/// use fmodeparser::{FullPermission, StatFormat};
+/// # use fmodeparser::FullPermissionError;
+/// # fn main() -> Result<(), FullPermissionError> {
+/// # let directory = tempfile::tempdir()?;
+/// # let path = directory.path().join("it's");
+/// # std::fs::write(&path, "")?;
+/// # FullPermission::new(0o100640)?.apply_to(&path)?;
+///
+/// let format: StatFormat = "%a %A %f %F %#05a".parse()?;
+/// assert_eq!(format.render(&path)?, "640 -rw-r----- 81a0 regular empty file 00640");
+///
+/// let link = directory.path().join("link");
+/// std::os::unix::fs::symlink("it's", &link)?;
+/// let format: StatFormat = "%N".parse()?;
+/// assert!(format.render(&link)?.ends_with(r#"/link' -> "it's""#));
+/// # Ok(())
+/// # }
+/// ```
+pub struct StatFormat {
+    format: String,
+    pieces: Vec<Piece>,
+    quote_names: bool,
+}
+
+impl StatFormat {
+    /// parses a format, the errors point at the first directive that is not known or whose
+    /// width or precision is larger than the `printf` limit.
+    pub fn new(format: &str) -> Result<Self, FullPermissionError> {
+        let chars: Vec<char> = format.chars().collect();
+        let mut pieces = Vec::new();
+        let mut text = String::new();
+        let mut index = 0;
+        while index < chars.len() {
+            if chars[index] != '%' {
+                text.push(chars[index]);
+                index += 1;
+                continue;
+            }
+            let start = index + 1;
+            let mut spec = Spec::default();
+            index = start;
+            while let Some(&flag) = chars.get(index).filter(|&&flag| FLAGS.contains(flag)) {
+                spec.left |= flag == '-';
+                spec.zero |= flag == '0';
+                spec.alternate |= flag == '#';
+                index += 1;
+            }
+            spec.width = read_number(format, &chars, &mut index, "width")?;
+            if chars.get(index) == Some(&'.') {
+                index += 1;
+                spec.precision = Some(read_number(format, &chars, &mut index, "precision")?);
+            }
+            match chars.get(index) {
+                // a lone `%` at the end and `%%` are written as they are
+                None | Some('%') if index == start => {
+                    text.push('%');
+                    index += 1;
+                }
+                Some(&code) if DIRECTIVES.contains(code) => {
+                    if !text.is_empty() {
+                        pieces.push(Piece::Text(std::mem::take(&mut text)));
+                    }
+                    pieces.push(Piece::Directive(spec, code));
+                    index += 1;
+                }
+                found => {
+                    return Err(FullPermissionError::invalid_symbol(
+                        format,
+                        index,
+                        found.copied(),
+                        DIRECTIVES,
+                    ))
+                }
+            }
+        }
+        if !text.is_empty() {
+            pieces.push(Piece::Text(text));
+        }
+        Ok(Self {
+            format: format.to_string(),
+            pieces,
+            // `stat` only picks its quoting style when the format contains a plain `%N`
+            quote_names: format.contains("%N"),
+        })
+    }
+    /// renders the format for a path like `stat`, a symbolic link is described itself.
+    pub fn render(&self, path: impl AsRef<Path>) -> Result<String, FullPermissionError> {
+        let path = path.as_ref();
+        let metadata = path
+            .symlink_metadata()
+            .map_err(|error| FullPermissionError::io(path, error))?;
+        self.render_metadata(path, &metadata)
+    }
+    /// renders the format for a path like `stat -L`, a symbolic link is followed.
+    pub fn render_following(&self, path: impl AsRef<Path>) -> Result<String, FullPermissionError> {
+        let path = path.as_ref();
+        let metadata = path
+            .metadata()
+            .map_err(|error| FullPermissionError::io(path, error))?;
+        self.render_metadata(path, &metadata)
+    }
+    /// renders the format for the metadata of a file, `name` is written by `%n` and `%N`
+    /// and the target of a symbolic link is read from it.
+    pub fn render_metadata(
+        &self,
+        name: impl AsRef<Path>,
+        metadata: &Metadata,
+    ) -> Result<String, FullPermissionError> {
+        let name = name.as_ref();
+        let permission = metadata.get_full_permission()?;
+        let mut output = String::new();
+        for piece in &self.pieces {
+            let (spec, code) = match piece {
+                Piece::Text(text) => {
+                    output.push_str(text);
+                    continue;
+                }
+                Piece::Directive(spec, code) => (spec, *code),
+            };
+            let rendered = match code {
+                'a' => spec.unsigned(u64::from(permission.get_mode() & 0o7777), 8),
+                'A' => spec.string(&permission.to_string()),
+                'f' => spec.unsigned(u64::from(permission.get_mode()), 16),
+                'F' => match permission.get_file_type() {
+                    FileType::Regular if metadata.len() == 0 => spec.string("regular empty file"),
+                    file_type => spec.string(file_type.description()),
+                },
+                'u' => spec.unsigned(u64::from(metadata.uid()), 10),
+                'U' => spec.string(&user_name(metadata.uid()).unwrap_or("UNKNOWN".into())),
+                'g' => spec.unsigned(u64::from(metadata.gid()), 10),
+                'G' => spec.string(&group_name(metadata.gid()).unwrap_or("UNKNOWN".into())),
+                't' => spec.unsigned(u64::from(device_numbers(metadata.rdev()).0), 16),
+                'T' => spec.unsigned(u64::from(device_numbers(metadata.rdev()).1), 16),
+                'h' => spec.unsigned(metadata.nlink(), 10),
+                'i' => spec.unsigned(metadata.ino(), 10),
+                'n' => spec.string(&name.as_os_str().to_string_lossy()),
+                _ => {
+                    let mut rendered = spec.string(&self.quote(name));
+                    if permission.get_file_type() == FileType::Symlink {
+                        let target = std::fs::read_link(name)
+                            .map_err(|error| FullPermissionError::io(name, error))?;
+                        rendered.push_str(" -> ");
+                        rendered.push_str(&spec.string(&self.quote(&target)));
+                    }
+                    rendered
+                }
+            };
+            output.push_str(&rendered);
+        }
+        Ok(output)
+    }
+    /// returns the name written by `%N`.
+    fn quote(&self, name: &Path) -> String {
+        if self.quote_names {
+            shell_quote(name.as_os_str().as_bytes())
+        } else {
+            name.as_os_str().to_string_lossy().into_owned()
+        }
+    }
+}
+
+/// reads the digits of a width or a precision, it fails above [`MAX_WIDTH`].
+fn read_number(
+    format: &str,
+    chars: &[char],
+    index: &mut usize,
+    name: &str,
+) -> Result<usize, FullPermissionError> {
+    let start = *index;
+    let mut number: usize = 0;
+    while let Some(digit) = chars.get(*index).and_then(|c| c.to_digit(10)) {
+        number = number.saturating_mul(10).saturating_add(digit as usize);
+        *index += 1;
+    }
+    if number > MAX_WIDTH {
+        return Err(FullPermissionError::InvalidFormat {
+            format: format.to_string(),
+            position: start,
+            reason: format!("the {name} is larger than {MAX_WIDTH}"),
+        });
+    }
+    Ok(number)
+}
+
+/// quotes a name like the `shell-escape-always` style `stat` uses for `%N` in the C locale.
+///
+/// the name is written between single quotes, a single quote is written `'\''` and the
+/// bytes that are not printable are written in `$'...'` escapes. a name whose only special
+/// characters are single quotes is written between double quotes instead: `"it's"`.
+fn shell_quote(name: &[u8]) -> String {
+    let mut quoted = String::from("'");
+    // true while a `$'...'` escape is open
+    let mut in_escape = false;
+    let mut double_quotes = true;
+    let mut has_single_quote = false;
+    for (index, &byte) in name.iter().enumerate() {
+        let escape = match byte {
+            0x07 => Some("\\a".to_string()),
+            0x08 => Some("\\b".to_string()),
+            0x0c => Some("\\f".to_string()),
+            b'\n' => Some("\\n".to_string()),
+            b'\r' => Some("\\r".to_string()),
+            b'\t' => Some("\\t".to_string()),
+            0x0b => Some("\\v".to_string()),
+            b' '..=b'~' => None,
+            _ => Some(format!("\\{byte:03o}")),
+        };
+        if let Some(escape) = escape {
+            if !in_escape {
+                quoted.push_str("'$'");
+                in_escape = true;
+            }
+            quoted.push_str(&escape);
+            double_quotes = false;
+            continue;
+        }
+        if byte == b'\'' {
+            has_single_quote = true;
+            quoted.push_str("'\\'");
+            in_escape = false;
+        } else if in_escape {
+            quoted.push_str("''");
+            in_escape = false;
+        }
+        quoted.push(byte as char);
+        double_quotes &= match byte {
+            b'{' | b'}' => name.len() == 1,
+            b'#' | b'~' => index == 0,
+            b'!' | b'"' | b'$' | b'&' | b'(' | b')' | b'*' | b';' | b'<' | b'=' | b'>' => false,
+            b'?' | b'[' | b'\\' | b'^' | b'`' | b'|' => false,
+            _ => true,
+        };
+    }
+    quoted.push('\'');
+    if has_single_quote && double_quotes {
+        return format!("\"{}\"", String::from_utf8_lossy(name));
+    }
+    quoted
+}
+
+impl FromStr for StatFormat {
+    type Err = FullPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        StatFormat::new(s)
+    }
+}
+
+impl TryFrom<&str> for StatFormat {
+    type Error = FullPermissionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        StatFormat::new(value)
+    }
+}
+
+/// writes the format as it was parsed.
+impl Display for StatFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.format)
+    }
+}
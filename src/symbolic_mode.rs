@@ -0,0 +1,262 @@
+//! This module contains the parser and evaluator of `chmod` symbolic mode expressions
+//! such as `u+x,g-w,o=r`, `a=rX`, `ug+s`, `o=u` or `+t`.
+//!
+
+use crate::{FileType, FullPermission, FullPermissionError};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// all the bits that `chmod` is allowed to change (special bits and the three classes).
+const CHMOD_MODE_BITS: u32 = 0o7777;
+/// the execute bits of the three classes.
+const EXECUTE_BITS: u32 = 0o111;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+/// how the value of a single change is computed when it is applied.
+enum ChangeKind {
+    /// the value is a fixed set of bits like `rw`.
+    Ordinary,
+    /// the value is copied from the current bits of a class like `o=u`.
+    CopyExisting,
+    /// the value contains the capital `X` which adds the execute bits only for directories
+    /// or when any execute bit is already set.
+    ExecuteIfAnyExecute,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// a single `who op perms` action of a clause.
+struct ModeChange {
+    op: char,
+    kind: ChangeKind,
+    /// the bits selected by the `who` part, `0` when it was omitted so the umask applies.
+    affected: u32,
+    /// the bits produced by the `perms` part before masking.
+    value: u32,
+    /// the bits that were explicitly mentioned, used to keep the setuid/setgid bits of a
+    /// directory unless asked for.
+    mentioned: u32,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// a parsed `chmod` symbolic mode expression.
+///
+/// the expression is a comma separated list of clauses, every clause has an optional `who`
+/// list (`u`, `g`, `o`, `a`) followed by one or more actions made of an operator
+/// (`+`, `-`, `=`) and either permission letters (`r`, `w`, `x`, `X`, `s`, `t`) or a single
+/// class to copy from (`u`, `g`, `o`).
+///
+/// clauses without a `who` part behave like `a` except that the bits set in the umask are
+/// left untouched, exactly like `chmod +x file` does.
+///
+/// # Example
+/// ```
+/// use fmodeparser::{FullPermission, SymbolicMode};
+/// # use fmodeparser::FullPermissionError;
+/// # fn main() -> Result<(), FullPermissionError> {
+///
+/// let expression: SymbolicMode = "u+x,g-w,o=r".parse()?;
+/// let mut permission = FullPermission::new(0o100664)?;
+/// expression.apply(&mut permission, 0o022)?;
+/// assert_eq!(permission.to_string(), "-rwxr--r--");
+///
+/// permission.apply_symbolic("a=rX", 0o022)?;
+/// assert_eq!(permission.to_string(), "-r-xr-xr-x");
+/// # Ok(())
+/// # }
+/// ```
+pub struct SymbolicMode {
+    expression: String,
+    changes: Vec<ModeChange>,
+}
+
+impl SymbolicMode {
+    /// applies the expression to a raw mode and returns the resulting mode.
+    ///
+    /// the file type bits of `mode` are kept and are used for the `X` rule and to preserve
+    /// the setuid/setgid bits of directories that were not explicitly mentioned.
+    pub fn apply_to_mode(&self, mode: u32, umask: u32) -> u32 {
+        let is_dir = FileType::from_bits(mode) == FileType::Directory;
+        let mut new_mode = mode & CHMOD_MODE_BITS;
+        for change in &self.changes {
+            let omit_change = if is_dir {
+                0o6000 & !change.mentioned
+            } else {
+                0
+            };
+            let mut value = change.value;
+            match change.kind {
+                ChangeKind::Ordinary => {}
+                ChangeKind::CopyExisting => {
+                    value &= new_mode;
+                    value = copy_to_all_classes(value);
+                }
+                ChangeKind::ExecuteIfAnyExecute => {
+                    if is_dir || new_mode & EXECUTE_BITS != 0 {
+                        value |= EXECUTE_BITS;
+                    }
+                }
+            }
+            let affected = if change.affected == 0 {
+                !umask
+            } else {
+                change.affected
+            };
+            value &= affected & !omit_change;
+            match change.op {
+                '=' => {
+                    let preserved = if change.affected == 0 {
+                        omit_change
+                    } else {
+                        !change.affected | omit_change
+                    };
+                    new_mode = (new_mode & preserved) | value;
+                }
+                '+' => new_mode |= value,
+                _ => new_mode &= !value,
+            }
+        }
+        (mode & !CHMOD_MODE_BITS) | (new_mode & CHMOD_MODE_BITS)
+    }
+    /// applies the expression to the permission in place.
+    ///
+    /// `umask` is only used by the clauses that have no `who` part like `+x`.
+    pub fn apply(
+        &self,
+        permission: &mut FullPermission,
+        umask: u32,
+    ) -> Result<(), FullPermissionError> {
+        let mode = self.apply_to_mode(permission.get_mode(), umask);
+        *permission = FullPermission::new(mode)?;
+        Ok(())
+    }
+}
+
+/// replicates every permission found in any class of `value` into all the three classes.
+fn copy_to_all_classes(value: u32) -> u32 {
+    let mut copied = 0;
+    for bits in [0o444, 0o222, 0o111] {
+        if value & bits != 0 {
+            copied |= bits;
+        }
+    }
+    copied
+}
+
+impl FromStr for SymbolicMode {
+    type Err = FullPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+        let mut changes = Vec::new();
+        let mut position = 0;
+        loop {
+            // the who part of the clause
+            let mut affected = 0;
+            while let Some(&who) = chars.get(position) {
+                affected |= match who {
+                    'u' => 0o4700,
+                    'g' => 0o2070,
+                    'o' => 0o1007,
+                    'a' => CHMOD_MODE_BITS,
+                    _ => break,
+                };
+                position += 1;
+            }
+            // every clause needs at least one action
+            match chars.get(position) {
+                Some('+' | '-' | '=') => {}
+                Some(&found) => {
+                    return Err(FullPermissionError::invalid_symbol(
+                        s,
+                        position,
+                        Some(found),
+                        "ugoa+-=",
+                    ))
+                }
+                None => {
+                    return Err(FullPermissionError::invalid_symbol(
+                        s, position, None, "+-=",
+                    ))
+                }
+            }
+            while let Some(&op) = chars.get(position) {
+                if !matches!(op, '+' | '-' | '=') {
+                    break;
+                }
+                position += 1;
+                let mut value = 0;
+                let mut kind = ChangeKind::Ordinary;
+                match chars.get(position) {
+                    Some(&class @ ('u' | 'g' | 'o')) => {
+                        value = match class {
+                            'u' => 0o700,
+                            'g' => 0o070,
+                            _ => 0o007,
+                        };
+                        kind = ChangeKind::CopyExisting;
+                        position += 1;
+                    }
+                    _ => {
+                        while let Some(&perm) = chars.get(position) {
+                            value |= match perm {
+                                'r' => 0o444,
+                                'w' => 0o222,
+                                'x' => 0o111,
+                                's' => 0o6000,
+                                't' => 0o1000,
+                                'X' => {
+                                    kind = ChangeKind::ExecuteIfAnyExecute;
+                                    0
+                                }
+                                _ => break,
+                            };
+                            position += 1;
+                        }
+                    }
+                }
+                let mentioned = if affected == 0 {
+                    value
+                } else {
+                    value & affected
+                };
+                changes.push(ModeChange {
+                    op,
+                    kind,
+                    affected,
+                    value,
+                    mentioned,
+                });
+            }
+            match chars.get(position) {
+                None => break,
+                Some(',') => position += 1,
+                Some(&found) => {
+                    return Err(FullPermissionError::invalid_symbol(
+                        s,
+                        position,
+                        Some(found),
+                        "rwxXst+-=,",
+                    ))
+                }
+            }
+        }
+        Ok(Self {
+            expression: s.to_string(),
+            changes,
+        })
+    }
+}
+
+impl TryFrom<&str> for SymbolicMode {
+    type Error = FullPermissionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for SymbolicMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
@@ -0,0 +1,195 @@
+//! This module contains the file mode creation mask of a process and the modes it gives
+//! to the files and directories created under it.
+//!
+
+use crate::{FileType, FullPermission, FullPermissionError, ModeBits, SymbolicMode};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// the bits a umask can clear, the special bits are never masked.
+const UMASK_BITS: u32 = 0o777;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+/// a file mode creation mask, the permissions it contains are removed from the mode
+/// requested when a file or a directory is created.
+///
+/// it is written in octal like `umask` prints it (`0022`), and [`to_symbolic`](Umask::to_symbolic)
+/// gives the allowed permissions like `umask -S` prints them (`u=rwx,g=rx,o=rx`).
+///
+/// # Example
+/// ```
+/// use fmodeparser::{FullPermission, Umask};
+/// # use fmodeparser::FullPermissionError;
+/// # fn main() -> Result<(), FullPermissionError> {
+///
+/// let umask: Umask = "027".parse()?;
+/// assert_eq!(umask.to_string(), "0027");
+/// assert_eq!(umask.to_symbolic(), "u=rwx,g=rx,o=");
+/// assert_eq!(umask.new_file_mode().to_string(), "-rw-r-----");
+/// assert_eq!(umask.new_directory_mode().to_string(), "drwxr-x---");
+///
+/// let requested = FullPermission::new(0o100755)?;
+/// assert_eq!(umask.apply(requested).mode_as_octal(), "100750");
+/// assert_eq!("UMask=0077".parse::<Umask>()?, "u=rwx,g=,o=".parse()?);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Umask {
+    bits: u32,
+}
+
+impl Umask {
+    /// returns the umask of the given bits, it fails when bits other than the permission
+    /// bits (`0o777`) are set.
+    pub fn new(bits: u32) -> Result<Self, FullPermissionError> {
+        if bits & !UMASK_BITS != 0 {
+            return Err(FullPermissionError::ModeOutOfRange {
+                kind: "umask",
+                value: bits,
+                allowed: UMASK_BITS,
+            });
+        }
+        Ok(Self { bits })
+    }
+    /// returns the masked permission bits.
+    pub fn get_bits(&self) -> u32 {
+        self.bits
+    }
+    /// reads the umask of the current process from `/proc/self/status`.
+    ///
+    /// unlike the `umask(2)` call it does not need to change the umask to read it, so it is
+    /// safe while other threads create files. it needs Linux 4.7 or newer.
+    pub fn current() -> Result<Self, FullPermissionError> {
+        Self::from_status_file(Path::new("/proc/self/status"))
+    }
+    /// reads the umask of any process from `/proc/<pid>/status`.
+    pub fn of_process(pid: u32) -> Result<Self, FullPermissionError> {
+        Self::from_status_file(&PathBuf::from(format!("/proc/{pid}/status")))
+    }
+    fn from_status_file(path: &Path) -> Result<Self, FullPermissionError> {
+        let content =
+            std::fs::read_to_string(path).map_err(|error| FullPermissionError::io(path, error))?;
+        let line = content
+            .lines()
+            .find_map(|line| line.strip_prefix("Umask:"))
+            .ok_or_else(|| {
+                let error = std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "the status has no `Umask:` line",
+                );
+                FullPermissionError::io(path, error)
+            })?;
+        line.trim().parse()
+    }
+    /// returns the `UMASK` of a `login.defs` file content, or `None` if it is not set.
+    pub fn from_login_defs(content: &str) -> Result<Option<Self>, FullPermissionError> {
+        for line in content.lines() {
+            let mut fields = line.split_whitespace();
+            if fields.next() == Some("UMASK") {
+                let value = fields.next().unwrap_or_default();
+                return value.parse().map(Some);
+            }
+        }
+        Ok(None)
+    }
+    /// returns the mode a file requested with `requested` gets once the umask is applied,
+    /// the file type and the special bits are kept.
+    pub fn apply(&self, requested: FullPermission) -> FullPermission {
+        requested.with_mode_bits(requested.get_mode_bits() & !ModeBits::from(self.bits))
+    }
+    /// returns the mode of a regular file created with the usual `0o666` mode.
+    pub fn new_file_mode(&self) -> FullPermission {
+        self.apply(permission_of(FileType::Regular, 0o666))
+    }
+    /// returns the mode of a directory created with the usual `0o777` mode.
+    pub fn new_directory_mode(&self) -> FullPermission {
+        self.apply(permission_of(FileType::Directory, 0o777))
+    }
+    /// returns the allowed permissions like `umask -S`: `u=rwx,g=rx,o=rx`.
+    pub fn to_symbolic(&self) -> String {
+        let allowed = permission_of(FileType::Regular, !self.bits & UMASK_BITS);
+        format!(
+            "u={},g={},o={}",
+            symbolic_class(allowed.user().get_partial_mode()),
+            symbolic_class(allowed.group().get_partial_mode()),
+            symbolic_class(allowed.other().get_partial_mode())
+        )
+    }
+    /// applies a symbolic expression of the allowed permissions like `umask g-w` does, the
+    /// classes that are not mentioned keep their mask.
+    pub fn with_symbolic(&self, expression: &str) -> Result<Self, FullPermissionError> {
+        let expression: SymbolicMode = expression.parse()?;
+        let allowed = expression.apply_to_mode(!self.bits & UMASK_BITS, 0);
+        Self::new(!allowed & UMASK_BITS)
+    }
+}
+
+/// returns the letters of the set permissions of a class digit, nothing when it is `0`.
+fn symbolic_class(digit: u32) -> String {
+    [(0o4, 'r'), (0o2, 'w'), (0o1, 'x')]
+        .into_iter()
+        .filter(|(bit, _)| digit & bit != 0)
+        .map(|(_, symbol)| symbol)
+        .collect()
+}
+
+/// returns the permission of the file type with the given permission bits.
+fn permission_of(file_type: FileType, bits: u32) -> FullPermission {
+    FullPermission::from(ModeBits::from(bits)).with_file_type(file_type)
+}
+
+/// accepts the octal form (`022`, `0022`, `0o022`), the `umask -S` form
+/// (`u=rwx,g=rx,o=rx`), and a systemd `UMask=0022` or `login.defs` `UMASK 022` line.
+impl FromStr for Umask {
+    type Err = FullPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        let value = value
+            .strip_prefix("UMask=")
+            .or_else(|| value.strip_prefix("UMASK"))
+            .map_or(value, str::trim);
+        if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit() || c == 'o') {
+            let digits = value.strip_prefix("0o").unwrap_or(value);
+            let bits = u32::from_str_radix(digits, 8).map_err(|source| {
+                FullPermissionError::InvalidNumber {
+                    input: value.to_string(),
+                    radix: 8,
+                    source,
+                }
+            })?;
+            return Self::new(bits);
+        }
+        Umask::default().with_symbolic(value)
+    }
+}
+
+impl TryFrom<&str> for Umask {
+    type Error = FullPermissionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<u32> for Umask {
+    type Error = FullPermissionError;
+
+    fn try_from(bits: u32) -> Result<Self, Self::Error> {
+        Umask::new(bits)
+    }
+}
+
+impl From<Umask> for u32 {
+    fn from(umask: Umask) -> Self {
+        umask.bits
+    }
+}
+
+/// writes the umask in octal like `umask` prints it: `0022`.
+impl Display for Umask {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04o}", self.bits)
+    }
+}
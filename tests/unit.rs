@@ -1,8 +1,17 @@
 use fmodeparser::PermStrParser;
-use fmodeparser::{FullPermission, FullPermissionBuilder};
+use fmodeparser::{
+    AccessMarker, ApplyOptions, ChmodTransaction, Class, FileType, FindPerm, FullPermission,
+    FullPermissionBuilder, FullPermissionError, Identity, Listing, ListingRow, ListingSize,
+    LongListing, ModeBits, ModeFormat, ModeParser, ModeRule, Operation, OwnedPermission,
+    OwnersKind, Perm, PermMatch, PermPattern, PermSet, PermissionSnapshot, RecursiveChmod,
+    SnapshotDiff, StatFormat, SymbolicMode, SymlinkPolicy, TimeStyle, TypeMismatch, Umask, Verdict,
+    WhoSet,
+};
 use regex::Regex;
+use std::error::Error;
 use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
 
 const FILE: &str = "out.txt";
 
@@ -45,7 +54,7 @@ fn test_file_with_full_permission() {
 
 #[test]
 fn full_permission_get_mode() {
-    let mut full_permission = FullPermission::new(33188).unwrap();
+    let full_permission = FullPermission::new(33188).unwrap();
     assert_eq!(full_permission.get_mode(), 33188);
 }
 //===========================================================
@@ -97,7 +106,6 @@ fn file_empty_patt() {
 }
 #[test]
 #[should_panic]
-
 fn more_than_one_t() {
     valid_regex("^[-dlcbps]$", "-dlc");
 }
@@ -124,7 +132,7 @@ fn full_permission_build_group_panic() {
 }
 #[test]
 fn full_permission_build_user_group_other() {
-    let mut full_permission = FullPermissionBuilder::new()
+    let full_permission = FullPermissionBuilder::new()
         .user("---")
         .group("---")
         .other("---")
@@ -136,7 +144,7 @@ fn full_permission_build_user_group_other() {
 }
 #[test]
 fn full_permission_build_compare_file() {
-    let mut full_permission = FullPermissionBuilder::new()
+    let full_permission = FullPermissionBuilder::new()
         .file_type('-')
         .user("rw-")
         .other("r--")
@@ -147,7 +155,7 @@ fn full_permission_build_compare_file() {
 }
 #[test]
 fn full_permission_mode_as_octal() {
-    let mut full_permission = FullPermissionBuilder::new()
+    let full_permission = FullPermissionBuilder::new()
         .file_type('-')
         .user("rw-")
         .other("r--")
@@ -171,12 +179,13 @@ fn check_decimal_to_octal() {
 }
 //==================================
 #[test]
+#[allow(deprecated)]
 fn full_permission_manipulate() {
     let mut full_permission = FullPermission::new(33188).unwrap();
     let previous_mode = full_permission.get_mode();
     let user = full_permission.get_user();
     assert_eq!(user.to_string(), "rw-");
-    user.set_read('-');
+    user.set_read(false);
     assert_ne!(full_permission.get_mode(), previous_mode);
     assert_eq!(full_permission.mode_as_octal(), "100244");
     assert_eq!(full_permission.to_string(), "--w-r--r--");
@@ -199,3 +208,1619 @@ fn check_full_permission_error2() {
     assert_eq!(object.unwrap_err().to_string(),"the mode that was provided is not valid 6 digit decimal number that can be parsed correctly into octal mode :14522125");
 }
 //=======================================================
+#[test]
+fn special_bits_setuid_binary() {
+    let full_permission = FullPermission::new(0o104755).unwrap();
+    assert_eq!(full_permission.to_string(), "-rwsr-xr-x");
+    assert!(full_permission.get_setuid());
+    assert_eq!(full_permission.get_special_mode(), 4);
+    assert_eq!(full_permission.get_mode(), 0o104755);
+    assert_eq!(full_permission.mode_as_octal(), "104755");
+}
+#[test]
+fn special_bits_sticky_directory() {
+    let full_permission = FullPermission::new(0o41777).unwrap();
+    assert_eq!(full_permission.get_file_type(), 'd');
+    assert_eq!(full_permission.to_string(), "drwxrwxrwt");
+    assert_eq!(full_permission.mode_as_octal(), "041777");
+}
+#[test]
+fn special_bits_uppercase_without_execute() {
+    let full_permission = FullPermission::new(0o107644).unwrap();
+    assert_eq!(full_permission.to_string(), "-rwSr-Sr-T");
+}
+#[test]
+fn special_bits_setters() {
+    let mut full_permission = FullPermission::new(0o102755).unwrap();
+    assert_eq!(full_permission.to_string(), "-rwxr-sr-x");
+    full_permission.set_setgid(false);
+    full_permission.set_setuid(true);
+    full_permission.set_sticky(true);
+    assert_eq!(full_permission.to_string(), "-rwsr-xr-t");
+    assert_eq!(full_permission.get_mode(), 0o105755);
+}
+//=======================================================
+#[test]
+fn parse_symbolic_round_trip() {
+    for perm in [
+        "-rw-r--r--",
+        "drwxrwxrwt",
+        "-rwsr-sr-x",
+        "lrwxrwxrwx",
+        "crw--w----",
+        "-rwSr-Sr-T",
+    ] {
+        let full_permission: FullPermission = perm.parse().unwrap();
+        assert_eq!(full_permission.to_string(), perm);
+    }
+}
+#[test]
+fn parse_symbolic_with_acl_marker() {
+    let full_permission = FullPermission::try_from("drwxr-x---+").unwrap();
+    assert_eq!(full_permission.get_mode(), 0o40750);
+    assert!("-rw-r--r--@".parse::<FullPermission>().is_ok());
+    assert!("-rw-r--r--.".parse::<FullPermission>().is_ok());
+}
+#[test]
+fn parse_symbolic_error_position() {
+    let error = "-rw-rz-r--".parse::<FullPermission>().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid character `z` at position 5 of `-rw-rz-r--`, expected one of `w-`"
+    );
+    let error = "-rw-r--r-s".parse::<FullPermission>().unwrap_err();
+    assert!(error.to_string().contains("position 9"));
+    assert!("-rw-r--r".parse::<FullPermission>().is_err());
+    assert!("-rw-r--r--!".parse::<FullPermission>().is_err());
+}
+#[test]
+fn try_from_u32() {
+    let full_permission = FullPermission::try_from(33188).unwrap();
+    assert_eq!(full_permission.to_string(), "-rw-r--r--");
+}
+//=======================================================
+fn chmod(mode: u32, expression: &str, umask: u32) -> String {
+    let mut full_permission = FullPermission::new(mode).unwrap();
+    full_permission.apply_symbolic(expression, umask).unwrap();
+    full_permission.mode_as_octal()
+}
+#[test]
+fn symbolic_mode_basic_clauses() {
+    assert_eq!(chmod(0o100664, "u+x,g-w,o=r", 0o022), "100744");
+    assert_eq!(chmod(0o100644, "ug+s", 0o022), "106644");
+    assert_eq!(chmod(0o040755, "+t", 0o022), "041755");
+    assert_eq!(chmod(0o100640, "o=u", 0o022), "100646");
+    assert_eq!(chmod(0o100750, "g=u-w", 0o022), "100750");
+    assert_eq!(chmod(0o100777, "a-rwx,u+rw", 0o022), "100600");
+}
+#[test]
+fn symbolic_mode_capital_x() {
+    assert_eq!(chmod(0o100644, "a+X", 0o022), "100644");
+    assert_eq!(chmod(0o100744, "a+X", 0o022), "100755");
+    assert_eq!(chmod(0o040600, "a=rX", 0o022), "040555");
+    assert_eq!(chmod(0o100600, "a=rX", 0o022), "100444");
+}
+#[test]
+fn symbolic_mode_umask_for_who_less_clauses() {
+    assert_eq!(chmod(0o100644, "+x", 0o022), "100755");
+    assert_eq!(chmod(0o100644, "+x", 0o077), "100744");
+    assert_eq!(chmod(0o100666, "-w", 0o022), "100466");
+    assert_eq!(chmod(0o100000, "=rw", 0o022), "100644");
+    assert_eq!(chmod(0o100000, "a=rw", 0o022), "100666");
+}
+#[test]
+fn symbolic_mode_directory_keeps_setgid() {
+    assert_eq!(chmod(0o042755, "a=rx", 0o022), "042555");
+    assert_eq!(chmod(0o042755, "g-s", 0o022), "040755");
+}
+#[test]
+fn symbolic_mode_errors() {
+    assert!("u+z".parse::<SymbolicMode>().is_err());
+    assert!("u".parse::<SymbolicMode>().is_err());
+    assert!("u+x,".parse::<SymbolicMode>().is_err());
+    let error = "u+x,k-w".parse::<SymbolicMode>().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid character `k` at position 4 of `u+x,k-w`, expected one of `ugoa+-=`"
+    );
+}
+//=======================================================
+fn parse_any(input: &str) -> (String, ModeFormat) {
+    let (full_permission, format) = FullPermission::parse_any(input).unwrap();
+    (full_permission.mode_as_octal(), format)
+}
+#[test]
+fn parse_any_numeric_forms() {
+    assert_eq!(parse_any("0644"), ("100644".to_string(), ModeFormat::Octal));
+    assert_eq!(
+        parse_any("0o755"),
+        ("100755".to_string(), ModeFormat::Octal)
+    );
+    assert_eq!(parse_any("4755"), ("104755".to_string(), ModeFormat::Octal));
+    assert_eq!(
+        parse_any("40755"),
+        ("040755".to_string(), ModeFormat::Octal)
+    );
+    assert_eq!(
+        parse_any("100644"),
+        ("100644".to_string(), ModeFormat::Octal)
+    );
+    assert_eq!(
+        parse_any("33188"),
+        ("100644".to_string(), ModeFormat::Decimal)
+    );
+    assert_eq!(
+        parse_any("33261"),
+        ("100755".to_string(), ModeFormat::Decimal)
+    );
+}
+#[test]
+fn parse_any_symbolic_forms() {
+    assert_eq!(
+        parse_any("rwxr-x---"),
+        ("100750".to_string(), ModeFormat::Symbolic)
+    );
+    assert_eq!(
+        parse_any("drwxr-xr-x"),
+        ("040755".to_string(), ModeFormat::Symbolic)
+    );
+    assert_eq!(
+        parse_any("u=rw,go=r"),
+        ("100644".to_string(), ModeFormat::Expression)
+    );
+}
+#[test]
+fn parse_any_ambiguous_needs_hint() {
+    let error = FullPermission::parse_any("420").unwrap_err();
+    assert!(error.to_string().contains("ambiguous"));
+    let (full_permission, format) =
+        FullPermission::parse_any_with_hint("420", ModeFormat::Decimal).unwrap();
+    assert_eq!(format, ModeFormat::Decimal);
+    assert_eq!(full_permission.to_string(), "-rw-r--r--");
+    let (full_permission, _) =
+        FullPermission::parse_any_with_hint("420", ModeFormat::Octal).unwrap();
+    assert_eq!(full_permission.to_string(), "-r---w----");
+    assert!(FullPermission::parse_any("").is_err());
+    assert!(FullPermission::parse_any("0o8").is_err());
+}
+//=======================================================
+#[test]
+fn mode_bits_of_full_permission() {
+    let full_permission = FullPermission::new(0o104750).unwrap();
+    let bits = full_permission.get_mode_bits();
+    assert!(bits.contains(ModeBits::SETUID | ModeBits::USER | ModeBits::GROUP_READ));
+    assert!(!bits.intersects(ModeBits::OTHER | ModeBits::GROUP_WRITE));
+    assert_eq!(bits.bits(), 0o4750);
+}
+#[test]
+fn setters_keep_mode_consistent() {
+    let mut full_permission = FullPermission::new(0o100000).unwrap();
+    full_permission.user_mut().set_read(true);
+    full_permission.user_mut().set_write(true);
+    full_permission.other_mut().set_execute(true);
+    assert_eq!(full_permission.get_mode(), 0o100601);
+    full_permission.user_mut().set_write(false);
+    assert_eq!(full_permission.to_string(), "-r-------x");
+}
+//=======================================================
+fn assert_value_type<T: Clone + Copy + std::hash::Hash + Ord + Send + Sync>() {}
+#[test]
+fn full_permission_is_value_type() {
+    assert_value_type::<FullPermission>();
+    let full_permission = FullPermission::new(0o100644).unwrap();
+    let shared = std::sync::Arc::new(full_permission);
+    let handle = std::thread::spawn(move || shared.get_mode());
+    assert_eq!(handle.join().unwrap(), 0o100644);
+}
+#[test]
+fn full_permission_with_builders() {
+    let base = FullPermission::new(0o100644).unwrap();
+    let changed = base
+        .with_user(7)
+        .with_group(5)
+        .with_other(0)
+        .with_setgid(true);
+    assert_eq!(changed.to_string(), "-rwxr-s---");
+    assert_eq!(base.to_string(), "-rw-r--r--");
+    assert!(base < changed);
+    assert_eq!(base.user().to_string(), "rw-");
+}
+#[test]
+fn full_permission_mut_accessors() {
+    let mut full_permission = FullPermission::new(0o100644).unwrap();
+    full_permission.group_mut().set_write(true);
+    full_permission.other_mut().set_partial_mode(0);
+    assert_eq!(full_permission.mode_as_octal(), "100660");
+}
+//=======================================================
+#[test]
+fn file_type_conversions() {
+    assert_eq!(FileType::from_bits(0o120777), FileType::Symlink);
+    assert_eq!(FileType::from_bits(0o644), FileType::Regular);
+    assert_eq!(FileType::try_from('b').unwrap(), FileType::BlockDevice);
+    assert!(FileType::try_from('x').is_err());
+    assert_eq!(char::from(FileType::Door), 'D');
+    assert_eq!(u32::from(FileType::Socket), 0o140000);
+    let std_type = Path::new("src").metadata().unwrap().file_type();
+    assert_eq!(FileType::from(std_type), FileType::Directory);
+}
+#[test]
+fn unknown_file_type_is_reported() {
+    let full_permission = FullPermission::new(0o070644).unwrap();
+    assert_eq!(full_permission.get_file_type(), FileType::Unknown(0o070000));
+    assert_eq!(full_permission.to_string(), "?rw-r--r--");
+    assert_eq!(full_permission.get_mode(), 0o070644);
+}
+#[test]
+fn builder_accepts_file_type_enum() {
+    let full_permission = FullPermissionBuilder::new()
+        .file_type(FileType::Fifo)
+        .user("rw-")
+        .group("---")
+        .other("---")
+        .build()
+        .unwrap();
+    assert_eq!(full_permission.to_string(), "prw-------");
+    assert_eq!(
+        Path::new("src")
+            .metadata()
+            .unwrap()
+            .convert_file_type()
+            .unwrap(),
+        FileType::Directory
+    );
+}
+//=======================================================
+#[test]
+fn typed_grant_revoke_assign() {
+    let mut full_permission = FullPermission::new(0o100666).unwrap();
+    full_permission.revoke(WhoSet::GROUP | WhoSet::OTHER, PermSet::WRITE);
+    assert_eq!(full_permission.to_string(), "-rw-r--r--");
+    full_permission.grant(WhoSet::ALL, Perm::Execute);
+    assert_eq!(full_permission.to_string(), "-rwxr-xr-x");
+    full_permission.assign(Class::Other, PermSet::empty());
+    assert_eq!(full_permission.mode_as_octal(), "100750");
+    assert!(full_permission.has(Class::Group, Perm::Read));
+    assert!(!full_permission.has(Class::Group, Perm::Write));
+}
+#[test]
+fn typed_index_by_class() {
+    let mut full_permission = FullPermission::new(0o100640).unwrap();
+    assert_eq!(full_permission[Class::User].to_string(), "rw-");
+    full_permission[Class::Other].set_partial_mode(4);
+    assert_eq!(full_permission.to_string(), "-rw-r--r--");
+}
+#[test]
+fn who_and_perm_sets_from_str() {
+    assert_eq!(
+        "ug".parse::<WhoSet>().unwrap(),
+        WhoSet::USER | WhoSet::GROUP
+    );
+    assert_eq!("a".parse::<WhoSet>().unwrap(), WhoSet::ALL);
+    assert_eq!("rx".parse::<PermSet>().unwrap().bits(), 5);
+    assert_eq!(PermSet::from_bits_truncate(6).to_string(), "rw");
+    assert!("uz".parse::<WhoSet>().is_err());
+    assert!("rwq".parse::<PermSet>().is_err());
+}
+//=======================================================
+#[test]
+fn bitwise_operators_keep_file_type() {
+    let directory = FullPermission::new(0o040777).unwrap();
+    let umask = FullPermission::new(0o022).unwrap();
+    assert_eq!((directory & !umask).mode_as_octal(), "040755");
+    assert_eq!((directory - umask).mode_as_octal(), "040755");
+    assert_eq!((directory & !0o022).mode_as_octal(), "040755");
+    let regular = FullPermission::new(0o100640).unwrap();
+    assert_eq!((regular | 0o004).mode_as_octal(), "100644");
+    assert_eq!((regular ^ 0o660).mode_as_octal(), "100020");
+    assert_eq!((!regular).mode_as_octal(), "107137");
+    let mut assigned = regular;
+    assigned |= ModeBits::OTHER_READ;
+    assigned -= ModeBits::USER_WRITE;
+    assert_eq!(assigned.to_string(), "-r--r--r--");
+}
+#[test]
+fn set_algebra_and_partial_order() {
+    let regular = FullPermission::new(0o100640).unwrap();
+    let wider = FullPermission::new(0o100644).unwrap();
+    assert!(regular.is_subset_of(wider));
+    assert!(wider.is_superset_of(regular));
+    assert!(!wider.is_subset_of(0o640));
+    assert_eq!(regular.intersection(0o604).mode_as_octal(), "100600");
+    assert_eq!(regular.union(0o004), wider);
+    assert_eq!(regular.subset_cmp(wider), Some(std::cmp::Ordering::Less));
+    assert_eq!(wider.subset_cmp(regular), Some(std::cmp::Ordering::Greater));
+    assert_eq!(regular.subset_cmp(0o704), None);
+}
+//=======================================================
+#[test]
+fn builder_is_order_independent() {
+    let full_permission = FullPermissionBuilder::new()
+        .other("r--")
+        .user("rw-")
+        .file_type('d')
+        .group("r-x")
+        .build()
+        .unwrap();
+    assert_eq!(full_permission.to_string(), "drw-r-xr--");
+    let partial = FullPermissionBuilder::new().group("rw-").build().unwrap();
+    assert_eq!(partial.mode_as_octal(), "100060");
+}
+#[test]
+fn builder_try_methods_return_errors() {
+    let mut builder = FullPermissionBuilder::new();
+    let error = builder.try_user("rwz").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid character `z` at position 2 of `rwz`, expected one of `x-sS`"
+    );
+    assert!(builder.try_file_type('q').is_err());
+    assert!(builder.try_other("rws").is_err());
+    assert!(builder.try_group_octal(8).is_err());
+    assert_eq!(builder.build().unwrap().to_string(), "----------");
+}
+#[test]
+fn builder_octal_and_special_bits() {
+    let full_permission = FullPermissionBuilder::new()
+        .user_octal(7)
+        .group_octal(5)
+        .other_octal(5)
+        .setgid(true)
+        .build()
+        .unwrap();
+    assert_eq!(full_permission.to_string(), "-rwxr-sr-x");
+    let sticky = FullPermissionBuilder::new()
+        .file_type(FileType::Directory)
+        .user("rwx")
+        .group("rwx")
+        .other("rwt")
+        .build()
+        .unwrap();
+    assert_eq!(sticky.mode_as_octal(), "041777");
+}
+#[test]
+fn builder_special_bits_do_not_depend_on_the_call_order() {
+    let setuid_first = FullPermissionBuilder::new()
+        .setuid(true)
+        .user("rwx")
+        .build()
+        .unwrap();
+    let user_first = FullPermissionBuilder::new()
+        .user("rwx")
+        .setuid(true)
+        .build()
+        .unwrap();
+    assert_eq!(setuid_first, user_first);
+    assert_eq!(setuid_first.to_string(), "-rws------");
+    let sticky = FullPermissionBuilder::new()
+        .file_type('d')
+        .sticky(true)
+        .other("r-x")
+        .group("r-s")
+        .build()
+        .unwrap();
+    assert_eq!(sticky.to_string(), "d---r-sr-t");
+}
+#[test]
+fn builder_seeded_from_full_permission() {
+    let full_permission = FullPermission::new(0o104755).unwrap();
+    let changed = full_permission.to_builder().other("---").build().unwrap();
+    assert_eq!(changed.to_string(), "-rwsr-x---");
+}
+#[test]
+fn builder_user_octal_out_of_range() {
+    let mut builder = FullPermissionBuilder::new();
+    assert!(matches!(
+        builder.try_user_octal(9),
+        Err(FullPermissionError::InvalidDigit { digit: 9 })
+    ));
+    assert_eq!(builder.build().unwrap().mode_as_octal(), "100000");
+}
+//=======================================================
+#[test]
+fn error_kinds_can_be_matched() {
+    let error = FullPermission::new(0o1000000).unwrap_err();
+    assert!(matches!(
+        error,
+        FullPermissionError::InvalidMode { value: 0o1000000 }
+    ));
+    let error = Umask::new(0o1022).unwrap_err();
+    assert!(matches!(
+        error,
+        FullPermissionError::ModeOutOfRange {
+            kind: "umask",
+            value: 0o1022,
+            allowed: 0o777
+        }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "the umask `1022` is out of range, only the bits of `777` are allowed"
+    );
+    let error = FindPerm::new(PermMatch::Exact, 0o170644).unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("the find mode `170644` is out of range"));
+    let error = "-rw-r--r".parse::<FullPermission>().unwrap_err();
+    assert!(matches!(
+        error,
+        FullPermissionError::InvalidSymbol {
+            position: 8,
+            found: None,
+            ..
+        }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "the input `-rw-r--r` ends at position 8, expected one of `w-`"
+    );
+    let error = "-rw-r--r--+x".parse::<FullPermission>().unwrap_err();
+    assert!(matches!(
+        error,
+        FullPermissionError::InvalidSymbol {
+            position: 11,
+            found: Some('x'),
+            ..
+        }
+    ));
+    let error = FullPermissionBuilder::new().try_user_octal(8).unwrap_err();
+    assert!(matches!(
+        error,
+        FullPermissionError::InvalidDigit { digit: 8 }
+    ));
+    let error = FileType::try_from_bits(0o070000).unwrap_err();
+    assert!(matches!(
+        error,
+        FullPermissionError::UnknownFileType { bits: 0o070000 }
+    ));
+    let error = FullPermission::parse_any("420").unwrap_err();
+    assert!(matches!(
+        error,
+        FullPermissionError::AmbiguousMode {
+            octal: 0o420,
+            decimal: 420,
+            ..
+        }
+    ));
+}
+#[test]
+fn error_keeps_source_and_path() {
+    let error = FullPermission::parse_any("0o8").unwrap_err();
+    assert!(matches!(
+        error,
+        FullPermissionError::InvalidNumber { radix: 8, .. }
+    ));
+    assert!(error.source().is_some());
+    let io_error = Path::new("does/not/exist").metadata().unwrap_err();
+    let error = FullPermissionError::io("does/not/exist", io_error);
+    assert_eq!(error.path(), Some(Path::new("does/not/exist")));
+    assert!(error.to_string().starts_with("does/not/exist: "));
+    let source = error.source().unwrap();
+    assert!(source.downcast_ref::<std::io::Error>().is_some());
+    let boxed: Box<dyn Error> = Box::new(FullPermission::new(0o1000000).unwrap_err());
+    let error = FullPermissionError::from(boxed);
+    assert!(matches!(error, FullPermissionError::InvalidMode { .. }));
+}
+#[test]
+fn mode_parser_from_str_is_validated() {
+    assert_eq!("rwx".parse::<ModeParser>().unwrap().get_partial_mode(), 7);
+    assert_eq!("r-S".parse::<ModeParser>().unwrap().get_partial_mode(), 4);
+    assert_eq!("--t".parse::<ModeParser>().unwrap().get_partial_mode(), 1);
+    assert_eq!(ModeParser::try_from("6").unwrap().to_string(), "rw-");
+    let error = "xwr".parse::<ModeParser>().unwrap_err();
+    assert!(matches!(
+        error,
+        FullPermissionError::InvalidSymbol {
+            position: 0,
+            found: Some('x'),
+            ..
+        }
+    ));
+    assert!(matches!(
+        "8".parse::<ModeParser>().unwrap_err(),
+        FullPermissionError::InvalidDigit { digit: 8 }
+    ));
+    assert!("rw".parse::<ModeParser>().is_err());
+    assert!("rwx-".parse::<ModeParser>().is_err());
+    assert!("".parse::<ModeParser>().is_err());
+}
+#[test]
+fn owners_kind_from_str_and_typed_setters() {
+    let mut owner: OwnersKind = "u=rws".parse().unwrap();
+    assert_eq!(owner.class(), Class::User);
+    assert_eq!(owner.get_partial_mode(), 7);
+    owner.set(Perm::Write, false);
+    owner.set_read(false);
+    assert!(owner.has(Perm::Execute) && !owner.has(Perm::Read));
+    assert_eq!(owner.to_string(), "--x");
+    assert_eq!(OwnersKind::try_from("o=5").unwrap().to_string(), "r-x");
+    assert!("o=rws".parse::<OwnersKind>().is_err());
+    assert!("g=rwt".parse::<OwnersKind>().is_err());
+    let error = "x=rwx".parse::<OwnersKind>().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid character `x` at position 0 of `x=rwx`, expected one of `ugo`"
+    );
+    assert!("urwx".parse::<OwnersKind>().is_err());
+}
+#[test]
+fn perm_str_parser_on_paths_files_and_entries() {
+    let expected = FullPermission::new(Path::new(FILE).metadata().unwrap().mode()).unwrap();
+    assert_eq!(Path::new(FILE).get_full_permission().unwrap(), expected);
+    assert_eq!(PathBuf::from(FILE).get_full_permission().unwrap(), expected);
+    let file = std::fs::File::open(FILE).unwrap();
+    assert_eq!(file.get_full_permission().unwrap(), expected);
+    assert_eq!(file.convert_permission_to_string().unwrap(), "-rw-r--r--");
+    let entry = std::fs::read_dir(".")
+        .unwrap()
+        .map(|entry| entry.unwrap())
+        .find(|entry| entry.file_name() == FILE)
+        .unwrap();
+    assert_eq!(entry.get_full_permission().unwrap(), expected);
+    assert_eq!(
+        Path::new("src").convert_file_type().unwrap(),
+        FileType::Directory
+    );
+}
+#[test]
+fn perm_str_parser_follows_symlinks_only_on_request() {
+    let directory = tempfile::tempdir().unwrap();
+    let link = directory.path().join("link");
+    std::os::unix::fs::symlink(std::fs::canonicalize(FILE).unwrap(), &link).unwrap();
+    let permission = link.get_full_permission().unwrap();
+    assert_eq!(permission.get_file_type(), FileType::Symlink);
+    let followed = link.get_full_permission_following().unwrap();
+    assert_eq!(followed.to_string(), "-rw-r--r--");
+    let missing = directory.path().join("missing");
+    let error = missing.get_full_permission().unwrap_err();
+    assert!(matches!(error, FullPermissionError::Io { .. }));
+    assert_eq!(error.path(), Some(missing.as_path()));
+}
+#[test]
+fn apply_to_writes_the_mode() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("file");
+    std::fs::write(&path, "").unwrap();
+    let permission = FullPermission::new(0o104750).unwrap();
+    permission.apply_to(&path).unwrap();
+    assert_eq!(path.get_full_permission().unwrap(), permission);
+    let file = std::fs::File::open(&path).unwrap();
+    let permission = FullPermission::new(0o100600).unwrap();
+    permission.apply_to_file(&file).unwrap();
+    assert_eq!(file.get_full_permission().unwrap(), permission);
+}
+#[test]
+fn apply_to_checks_the_file_type() {
+    let directory = tempfile::tempdir().unwrap();
+    let before = directory.path().get_full_permission().unwrap();
+    let permission = FullPermission::new(0o100700).unwrap();
+    let error = permission.apply_to(directory.path()).unwrap_err();
+    assert!(matches!(error, FullPermissionError::Policy { .. }));
+    assert_eq!(error.path(), Some(directory.path()));
+    assert_eq!(directory.path().get_full_permission().unwrap(), before);
+    let options = ApplyOptions::new().on_type_mismatch(TypeMismatch::Ignore);
+    let permission = FullPermission::new(0o100750).unwrap();
+    let outcome = permission
+        .apply_to_with_options(directory.path(), options)
+        .unwrap();
+    assert!(outcome.get_warning().is_none());
+    let applied = directory.path().get_full_permission().unwrap();
+    assert_eq!(applied.to_string(), "drwxr-x---");
+    let options = ApplyOptions::new().on_type_mismatch(TypeMismatch::Warn);
+    let permission = FullPermission::new(0o100700).unwrap();
+    let warning = permission
+        .apply_to_with_options(directory.path(), options)
+        .unwrap()
+        .into_warning()
+        .unwrap();
+    assert_eq!(warning.path(), Some(directory.path()));
+    let applied = directory.path().get_full_permission().unwrap();
+    assert_eq!(applied.to_string(), "drwx------");
+}
+#[test]
+fn apply_to_reads_modes_without_a_file_type_as_regular_files() {
+    let directory = tempfile::tempdir().unwrap();
+    let (permission, _) = FullPermission::parse_any("755").unwrap();
+    assert_eq!(permission, FullPermission::new(0o100755).unwrap());
+    let error = permission.apply_to(directory.path()).unwrap_err();
+    assert!(matches!(error, FullPermissionError::Policy { .. }));
+    let options = ApplyOptions::new().on_type_mismatch(TypeMismatch::Ignore);
+    permission
+        .apply_to_with_options(directory.path(), options)
+        .unwrap();
+    let applied = directory.path().get_full_permission().unwrap();
+    assert_eq!(applied.to_string(), "drwxr-xr-x");
+    let (permission, _) = FullPermission::parse_any("u=rwx,g=rx,o=").unwrap();
+    assert_eq!(permission.get_mode(), 0o100750);
+    permission
+        .apply_to_with_options(directory.path(), options)
+        .unwrap();
+    let applied = directory.path().get_full_permission().unwrap();
+    assert_eq!(applied.to_string(), "drwxr-x---");
+}
+#[test]
+fn apply_to_no_follow_does_not_touch_the_target() {
+    let directory = tempfile::tempdir().unwrap();
+    let target = directory.path().join("target");
+    std::fs::write(&target, "").unwrap();
+    FullPermission::new(0o100600)
+        .unwrap()
+        .apply_to(&target)
+        .unwrap();
+    let link = directory.path().join("link");
+    std::os::unix::fs::symlink(&target, &link).unwrap();
+    let permission = FullPermission::new(0o120644).unwrap();
+    // linux cannot change the mode of a link, the target must be left untouched anyway
+    let _ = permission.apply_to_with_options(&link, ApplyOptions::new().no_follow(true));
+    assert_eq!(target.get_full_permission().unwrap().get_mode(), 0o100600);
+    let error = FullPermission::new(0o100644)
+        .unwrap()
+        .apply_to_with_options(&link, ApplyOptions::new().no_follow(true))
+        .unwrap_err();
+    assert!(matches!(error, FullPermissionError::Policy { .. }));
+}
+#[test]
+fn std_permissions_conversions() {
+    use std::os::unix::fs::PermissionsExt;
+    let permission = FullPermission::new(0o102755).unwrap();
+    let permissions = std::fs::Permissions::from(permission);
+    assert_eq!(permissions.mode(), 0o2755);
+    let metadata_permissions = Path::new(FILE).metadata().unwrap().permissions();
+    let converted = FullPermission::try_from(metadata_permissions).unwrap();
+    assert_eq!(converted.to_string(), "-rw-r--r--");
+}
+//=======================================================
+fn chmod_tree() -> tempfile::TempDir {
+    let directory = tempfile::tempdir().unwrap();
+    let root = directory.path();
+    std::fs::create_dir_all(root.join("bin")).unwrap();
+    std::fs::create_dir_all(root.join(".git/objects")).unwrap();
+    std::fs::write(root.join("bin/run.sh"), "").unwrap();
+    std::fs::write(root.join("bin/tool"), "").unwrap();
+    std::fs::write(root.join("README"), "").unwrap();
+    std::fs::write(root.join(".git/config"), "").unwrap();
+    for path in ["bin/run.sh", "bin/tool", "README", ".git/config"] {
+        FullPermission::new(0o100600)
+            .unwrap()
+            .apply_to(root.join(path))
+            .unwrap();
+    }
+    FullPermission::new(0o100700)
+        .unwrap()
+        .apply_to(root.join("bin/tool"))
+        .unwrap();
+    for path in ["", "bin", ".git", ".git/objects"] {
+        FullPermission::new(0o40755)
+            .unwrap()
+            .apply_to(root.join(path))
+            .unwrap();
+    }
+    directory
+}
+fn mode_of(path: PathBuf) -> String {
+    path.convert_permission_to_string().unwrap()
+}
+#[test]
+fn recursive_chmod_octal_rules_keep_directory_set_id_bits() {
+    let directory = chmod_tree();
+    let root = directory.path();
+    FullPermission::new(0o42775)
+        .unwrap()
+        .apply_to(root.join("bin"))
+        .unwrap();
+    FullPermission::new(0o102755)
+        .unwrap()
+        .apply_to(root.join("bin/tool"))
+        .unwrap();
+    let run = |rule: &str| {
+        RecursiveChmod::new(root.join("bin"))
+            .rule(rule.parse::<ModeRule>().unwrap())
+            .run()
+            .unwrap();
+    };
+    run("750");
+    // like GNU `chmod -R 750`, only the directory keeps its setgid bit
+    assert_eq!(mode_of(root.join("bin")), "drwxr-s---");
+    assert_eq!(mode_of(root.join("bin/tool")), "-rwxr-x---");
+    run("1750");
+    assert_eq!(mode_of(root.join("bin")), "drwxr-s--T");
+    run("00750");
+    assert_eq!(mode_of(root.join("bin")), "drwxr-x---");
+    assert_eq!("00750".parse::<ModeRule>().unwrap().to_string(), "00750");
+    assert_eq!(
+        ModeRule::Mode(ModeBits::from(0o750)).apply_to_mode(0o46755, 0),
+        ModeRule::Expression("u=rwx,g=rx,o=".parse().unwrap()).apply_to_mode(0o46755, 0)
+    );
+}
+#[test]
+fn recursive_chmod_separate_rules_and_globs() {
+    let directory = chmod_tree();
+    let root = directory.path();
+    let report = RecursiveChmod::new(root)
+        .directory_rule("750".parse::<ModeRule>().unwrap())
+        .file_rule("go=rX".parse::<ModeRule>().unwrap())
+        .exclude(".git")
+        .unwrap()
+        .run()
+        .unwrap();
+    assert!(report.is_success());
+    assert_eq!(mode_of(root.join("bin")), "drwxr-x---");
+    assert_eq!(mode_of(root.join("bin/tool")), "-rwxr-xr-x");
+    assert_eq!(mode_of(root.join("bin/run.sh")), "-rw-r--r--");
+    assert_eq!(mode_of(root.join(".git/config")), "-rw-------");
+    let report = RecursiveChmod::new(root)
+        .file_rule(ModeBits::USER | ModeBits::GROUP_READ)
+        .include("**/*.sh")
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(report.get_changes().len(), 1);
+    assert_eq!(mode_of(root.join("bin/run.sh")), "-rwxr-----");
+    assert_eq!(mode_of(root.join("README")), "-rw-r--r--");
+    assert!(RecursiveChmod::new(root).include("[").is_err());
+}
+#[test]
+fn recursive_chmod_x_rule_and_dry_run() {
+    let directory = chmod_tree();
+    let root = directory.path();
+    let report = RecursiveChmod::new(root)
+        .rule("a+rX".parse::<ModeRule>().unwrap())
+        .dry_run(true)
+        .run()
+        .unwrap();
+    let readme = report
+        .get_changes()
+        .iter()
+        .find(|change| change.get_path() == root.join("README"))
+        .unwrap();
+    assert_eq!(readme.get_before().to_string(), "-rw-------");
+    assert_eq!(readme.get_after().to_string(), "-rw-r--r--");
+    let tool = report
+        .get_changes()
+        .iter()
+        .find(|change| change.get_path().ends_with("bin/tool"))
+        .unwrap();
+    assert_eq!(tool.get_after().to_string(), "-rwxr-xr-x");
+    assert_eq!(mode_of(root.join("README")), "-rw-------");
+}
+#[test]
+fn recursive_chmod_symlink_policy() {
+    let directory = chmod_tree();
+    let root = directory.path();
+    std::os::unix::fs::symlink(root.join("README"), root.join("link")).unwrap();
+    std::os::unix::fs::symlink(root, root.join("bin/loop")).unwrap();
+    let rule = "o+r".parse::<ModeRule>().unwrap();
+    let report = RecursiveChmod::new(root)
+        .file_rule(rule.clone())
+        .symlinks(SymlinkPolicy::Error)
+        .continue_on_error(true)
+        .run()
+        .unwrap();
+    assert_eq!(report.get_errors().len(), 2);
+    assert!(report
+        .get_errors()
+        .iter()
+        .all(|error| matches!(error, FullPermissionError::Policy { .. })));
+    let error = RecursiveChmod::new(root)
+        .file_rule(rule)
+        .symlinks(SymlinkPolicy::Error)
+        .run()
+        .unwrap_err();
+    assert_eq!(error.path(), Some(root.join("bin/loop").as_path()));
+    // the link back to the root does not walk the tree twice
+    let report = RecursiveChmod::new(root)
+        .directory_rule("o-r".parse::<ModeRule>().unwrap())
+        .symlinks(SymlinkPolicy::Follow)
+        .run()
+        .unwrap();
+    assert!(report.is_success());
+    assert_eq!(report.get_changes().len(), 4);
+    assert_eq!(mode_of(root.join("bin")), "drwxr-x--x");
+}
+//=======================================================
+#[test]
+fn chmod_transaction_commit_and_undo() {
+    let directory = chmod_tree();
+    let root = directory.path();
+    let journal = root.join("chmod.journal");
+    let mut transaction = ChmodTransaction::begin(&journal).unwrap();
+    let walker = RecursiveChmod::new(root.join("bin")).rule("go=".parse::<ModeRule>().unwrap());
+    let report = transaction.run(&walker).unwrap();
+    assert_eq!(report.get_changes().len(), 1);
+    transaction
+        .apply(root.join("README"), FullPermission::new(0o100400).unwrap())
+        .unwrap();
+    assert_eq!(transaction.commit().unwrap(), journal);
+    assert_eq!(mode_of(root.join("bin")), "drwx------");
+    assert_eq!(mode_of(root.join("README")), "-r--------");
+    let content = std::fs::read_to_string(&journal).unwrap();
+    assert!(content.starts_with("# fmodeparser chmod journal\n040755 "));
+    assert!(content.ends_with("commit\n"));
+    ChmodTransaction::undo(&journal).unwrap();
+    assert_eq!(mode_of(root.join("bin")), "drwxr-xr-x");
+    assert_eq!(mode_of(root.join("README")), "-rw-------");
+    assert!(ChmodTransaction::begin(&journal).is_err());
+}
+#[test]
+fn chmod_transaction_rolls_back_on_failure() {
+    let directory = chmod_tree();
+    let root = directory.path();
+    let journal = root.join("chmod.journal");
+    let mut transaction = ChmodTransaction::begin(&journal).unwrap();
+    transaction
+        .apply(root.join("README"), FullPermission::new(0o100644).unwrap())
+        .unwrap();
+    assert_eq!(mode_of(root.join("README")), "-rw-r--r--");
+    // a file permission applied to a directory is refused and undoes the previous change
+    let error = transaction
+        .apply(root.join("bin"), FullPermission::new(0o100700).unwrap())
+        .unwrap_err();
+    assert!(matches!(error, FullPermissionError::Policy { .. }));
+    assert_eq!(mode_of(root.join("README")), "-rw-------");
+    assert!(!journal.exists());
+    assert!(transaction
+        .apply(root.join("README"), FullPermission::new(0o100644).unwrap())
+        .is_err());
+    let mut transaction = ChmodTransaction::begin(&journal).unwrap();
+    transaction
+        .apply(root.join("README"), FullPermission::new(0o100444).unwrap())
+        .unwrap();
+    drop(transaction);
+    assert_eq!(mode_of(root.join("README")), "-rw-------");
+    assert!(!journal.exists());
+}
+#[test]
+fn chmod_transaction_journal_errors_name_the_line() {
+    let directory = tempfile::tempdir().unwrap();
+    let journal = directory.path().join("chmod.journal");
+    std::fs::write(&journal, "# fmodeparser chmod journal\n100644\n").unwrap();
+    let error = ChmodTransaction::undo(&journal).unwrap_err();
+    assert!(matches!(
+        error,
+        FullPermissionError::InvalidLine { line: 2, .. }
+    ));
+}
+//=======================================================
+#[test]
+fn permission_snapshot_round_trip_and_restore() {
+    let directory = chmod_tree();
+    let root = directory.path();
+    std::fs::write(root.join("odd name\n"), "").unwrap();
+    FullPermission::new(0o100644)
+        .unwrap()
+        .apply_to(root.join("odd name\n"))
+        .unwrap();
+    std::os::unix::fs::symlink("README", root.join("link")).unwrap();
+    let snapshot = PermissionSnapshot::capture(root).unwrap();
+    let text = snapshot.to_string();
+    let mut lines = text.lines();
+    assert_eq!(lines.next(), Some("# fmodeparser snapshot"));
+    assert!(lines.next().unwrap().starts_with("040755 "));
+    assert!(text.contains(" odd\\040name\\012\n"));
+    assert!(text.contains("120777 "));
+    assert_eq!(text.parse::<PermissionSnapshot>().unwrap(), snapshot);
+    let readme = snapshot.get("README").unwrap();
+    assert_eq!(readme.get_permission().to_string(), "-rw-------");
+    RecursiveChmod::new(root)
+        .rule("a+rX".parse::<ModeRule>().unwrap())
+        .run()
+        .unwrap();
+    assert_eq!(mode_of(root.join("README")), "-rw-r--r--");
+    snapshot.restore(root).unwrap();
+    assert_eq!(mode_of(root.join("README")), "-rw-------");
+    assert_eq!(PermissionSnapshot::capture(root).unwrap(), snapshot);
+    // a file replaced by a link is skipped instead of changing what the link points to
+    let outside = tempfile::tempdir().unwrap();
+    let target = outside.path().join(FILE);
+    std::fs::write(&target, "").unwrap();
+    FullPermission::new(0o100600)
+        .unwrap()
+        .apply_to(&target)
+        .unwrap();
+    std::fs::remove_file(root.join("odd name\n")).unwrap();
+    std::os::unix::fs::symlink(&target, root.join("odd name\n")).unwrap();
+    snapshot.restore(root).unwrap();
+    assert_eq!(mode_of(target), "-rw-------");
+}
+#[test]
+#[cfg(target_os = "linux")]
+fn permission_snapshot_restores_a_directory_before_its_content() {
+    let directory = tempfile::tempdir().unwrap();
+    let root = directory.path().to_path_buf();
+    std::fs::create_dir(root.join("sub")).unwrap();
+    std::fs::write(root.join("sub/f"), "").unwrap();
+    // root is not stopped by the modes, the restore then runs as `nobody` in its own thread
+    let nobody = 65534;
+    let as_root = Identity::current().unwrap().is_root();
+    if as_root {
+        for path in [&root, &root.join("sub"), &root.join("sub/f")] {
+            std::os::unix::fs::chown(path, Some(nobody), Some(nobody)).unwrap();
+        }
+    }
+    FullPermission::new(0o040700)
+        .unwrap()
+        .apply_to(root.join("sub"))
+        .unwrap();
+    FullPermission::new(0o100640)
+        .unwrap()
+        .apply_to(root.join("sub/f"))
+        .unwrap();
+    let snapshot = PermissionSnapshot::capture(&root).unwrap();
+    FullPermission::new(0o100600)
+        .unwrap()
+        .apply_to(root.join("sub/f"))
+        .unwrap();
+    FullPermission::new(0o040000)
+        .unwrap()
+        .apply_to(root.join("sub"))
+        .unwrap();
+    let restore_root = root.clone();
+    let restored = std::thread::spawn(move || {
+        if as_root {
+            // SAFETY: the filesystem ids only change for this thread, which ends right after
+            unsafe {
+                libc::setfsgid(nobody);
+                libc::setfsuid(nobody);
+            }
+            assert!(std::fs::read_dir(restore_root.join("sub")).is_err());
+        }
+        snapshot.restore(&restore_root)
+    });
+    restored.join().unwrap().unwrap();
+    assert_eq!(mode_of(root.join("sub")), "drwx------");
+    assert_eq!(mode_of(root.join("sub/f")), "-rw-r-----");
+}
+#[test]
+fn permission_snapshot_diff() {
+    let directory = chmod_tree();
+    let root = directory.path();
+    let before = PermissionSnapshot::capture(root).unwrap();
+    std::fs::remove_file(root.join("README")).unwrap();
+    std::fs::write(root.join("NEW"), "").unwrap();
+    FullPermission::new(0o100755)
+        .unwrap()
+        .apply_to(root.join("bin/run.sh"))
+        .unwrap();
+    let after = PermissionSnapshot::capture(root).unwrap();
+    let diff = before.diff(&after);
+    assert_eq!(diff.len(), 3);
+    assert!(matches!(&diff[0], SnapshotDiff::Added(entry) if entry.get_path() == Path::new("NEW")));
+    assert!(
+        matches!(&diff[1], SnapshotDiff::Removed(entry) if entry.get_path() == Path::new("README"))
+    );
+    let changed = diff[2].to_string();
+    assert!(changed.starts_with("~ bin/run.sh: -rw------- "));
+    assert!(changed.contains(" -> -rwxr-xr-x "));
+    let error = "# fmodeparser snapshot\n100644 0 0 a\n100644 x 0 b\n"
+        .parse::<PermissionSnapshot>()
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        FullPermissionError::InvalidLine { line: 3, .. }
+    ));
+}
+//=======================================================
+#[test]
+fn umask_parsing_and_formatting() {
+    let umask: Umask = "0022".parse().unwrap();
+    assert_eq!(umask.get_bits(), 0o022);
+    assert_eq!(umask.to_symbolic(), "u=rwx,g=rx,o=rx");
+    assert_eq!("u=rwx,g=rx,o=rx".parse::<Umask>().unwrap(), umask);
+    assert_eq!("UMASK\t\t077".parse::<Umask>().unwrap().to_string(), "0077");
+    assert_eq!(
+        Umask::try_from(0o002).unwrap().to_symbolic(),
+        "u=rwx,g=rwx,o=rx"
+    );
+    assert_eq!(umask.with_symbolic("g-x").unwrap().to_string(), "0032");
+    let login_defs = "# the default umask\n#UMASK 000\nUMASK\t\t027\nUSERGROUPS_ENAB yes\n";
+    assert_eq!(
+        Umask::from_login_defs(login_defs).unwrap(),
+        Some(Umask::new(0o027).unwrap())
+    );
+    assert_eq!(Umask::from_login_defs("MAIL_DIR /var/mail").unwrap(), None);
+    assert!(Umask::new(0o1022).is_err());
+    assert!("0888".parse::<Umask>().is_err());
+    assert!("u=rwz".parse::<Umask>().is_err());
+}
+#[test]
+fn umask_of_the_process_and_effective_modes() {
+    let umask = Umask::current().unwrap();
+    assert_eq!(Umask::of_process(std::process::id()).unwrap(), umask);
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("created");
+    std::fs::write(&path, "").unwrap();
+    assert_eq!(path.get_full_permission().unwrap(), umask.new_file_mode());
+    let umask = Umask::new(0o077).unwrap();
+    let requested = FullPermission::new(0o104755).unwrap();
+    assert_eq!(umask.apply(requested).to_string(), "-rws------");
+    assert_eq!(umask.new_directory_mode().mode_as_octal(), "040700");
+}
+//=======================================================
+#[test]
+fn long_listing_aligns_the_columns_like_gnu_ls() {
+    let directory = tempfile::tempdir().unwrap();
+    let root = directory.path();
+    let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    std::fs::write(root.join("big"), vec![0u8; 12345]).unwrap();
+    std::fs::hard_link(root.join("big"), root.join("big.bak")).unwrap();
+    std::fs::write(root.join("small"), "1").unwrap();
+    std::fs::write(root.join(".hidden"), "").unwrap();
+    std::os::unix::fs::symlink("small", root.join("link")).unwrap();
+    FullPermission::new(0o100640)
+        .unwrap()
+        .apply_to(root.join("big"))
+        .unwrap();
+    FullPermission::new(0o104755)
+        .unwrap()
+        .apply_to(root.join("small"))
+        .unwrap();
+    let file = std::fs::File::open(root.join("big")).unwrap();
+    file.set_modified(now - Duration::from_secs(86_400))
+        .unwrap();
+    let file = std::fs::File::open(root.join("small")).unwrap();
+    file.set_modified(UNIX_EPOCH + Duration::from_secs(1_600_000_000))
+        .unwrap();
+    let metadata = root.metadata().unwrap();
+    let (uid, gid) = (metadata.uid(), metadata.gid());
+
+    let listing = LongListing::new()
+        .numeric_ids(true)
+        .time_style("+old\nrecent".parse().unwrap())
+        .now(now)
+        .list(root)
+        .unwrap();
+    assert!(listing.get_total().is_some());
+    let text = listing.to_string();
+    let lines: Vec<&str> = text.lines().skip(1).collect();
+    assert_eq!(
+        lines.join("\n").replace(&format!("{uid} {gid}"), "U G"),
+        [
+            "-rw-r----- 2 U G 12345 recent big",
+            "-rw-r----- 2 U G 12345 recent big.bak",
+            "lrwxrwxrwx 1 U G     5 old link -> small",
+            "-rwsr-xr-x 1 U G     1 old small",
+        ]
+        .join("\n")
+    );
+    let row = &listing.get_rows()[2];
+    assert_eq!(row.get_permission().get_file_type(), FileType::Symlink);
+    assert_eq!(row.get_size(), ListingSize::Bytes(5));
+    assert_eq!(row.get_target(), Some("small"));
+    assert_eq!(row.get_access_marker(), None);
+}
+#[test]
+fn long_listing_options() {
+    let directory = tempfile::tempdir().unwrap();
+    let root = directory.path();
+    std::fs::write(root.join(".hidden"), "").unwrap();
+    std::fs::write(root.join("visible"), "").unwrap();
+    FullPermission::new(0o100644)
+        .unwrap()
+        .apply_to(root.join("visible"))
+        .unwrap();
+    let names = |listing: &Listing| -> Vec<String> {
+        let rows = listing.get_rows().iter();
+        rows.map(|row| row.get_name().to_string()).collect()
+    };
+    let listing = LongListing::new().all(true).list(root).unwrap();
+    assert_eq!(names(&listing), [".", "..", ".hidden", "visible"]);
+    let listing = LongListing::new().directory(true).list(root).unwrap();
+    assert_eq!(listing.get_total(), None);
+    assert_eq!(names(&listing), [root.to_string_lossy()]);
+    assert!(listing.to_string().starts_with("drwx"));
+
+    let listing = LongListing::new()
+        .time_style(TimeStyle::LongIso)
+        .list(root.join("visible"))
+        .unwrap();
+    let pattern = Regex::new(r"^-rw-r--r-- 1 \S+ \S+ 0 \d{4}-\d\d-\d\d \d\d:\d\d /\S+/visible\n$");
+    assert!(pattern.unwrap().is_match(&listing.to_string()));
+    let listing = LongListing::new()
+        .numeric_ids(true)
+        .list("/dev/null")
+        .unwrap();
+    let row = &listing.get_rows()[0];
+    let metadata = std::fs::metadata("/dev/null").unwrap();
+    assert_eq!(row.get_permission().get_mode(), metadata.mode());
+    assert_eq!(row.get_permission().get_file_type(), FileType::CharDevice);
+    assert!(matches!(row.get_size(), ListingSize::Device { .. }));
+    assert_eq!(row.get_owner(), metadata.uid().to_string());
+    assert_eq!(row.get_group(), metadata.gid().to_string());
+
+    assert_eq!("full-iso".parse::<TimeStyle>().unwrap(), TimeStyle::FullIso);
+    assert_eq!(TimeStyle::Format("%s".into()).to_string(), "+%s");
+    assert!(matches!(
+        "short".parse::<TimeStyle>(),
+        Err(FullPermissionError::InvalidTimeStyle { .. })
+    ));
+    assert!(LongListing::new().list(root.join("missing")).is_err());
+}
+//=======================================================
+#[test]
+fn parse_gnu_and_bsd_listing_lines() {
+    let gnu = "total 12\n\
+        crw-rw-rw-  1 root root 1, 3 Oct 17 09:36 null\n\
+        -rw-r--r--+ 1 1000 1000   10 2020-01-02 03:04 my notes.txt\n\
+        drwxr-x---. 2 root root 4096 2026-10-18 05:46:01.123456789 +0000 sub\n\
+        -rw-------  1 root root  200 2020-01-02  old\n\
+        lrwxrwxrwx  1 root root    6 Jan  2  2020 current -> config\n";
+    let listing: Listing = gnu.parse().unwrap();
+    assert_eq!(listing.get_total(), Some(12));
+    let rows = listing.get_rows();
+    assert_eq!(rows.len(), 5);
+    assert_eq!(
+        rows[0].get_size(),
+        ListingSize::Device { major: 1, minor: 3 }
+    );
+    assert_eq!(rows[1].get_access_marker(), Some(AccessMarker::Acl));
+    assert_eq!(rows[1].get_owner(), "1000");
+    assert_eq!(rows[1].get_name(), "my notes.txt");
+    assert_eq!(
+        rows[2].get_access_marker(),
+        Some(AccessMarker::SecurityContext)
+    );
+    assert_eq!(rows[2].get_time(), "2026-10-18 05:46:01.123456789 +0000");
+    assert_eq!(rows[3].get_time(), "2020-01-02 ");
+    assert_eq!(rows[3].get_name(), "old");
+    assert_eq!(rows[4].get_time(), "Jan  2  2020");
+    assert_eq!(rows[4].get_target(), Some("config"));
+    assert_eq!(listing.to_string(), gnu);
+
+    let bsd: ListingRow = "-rwxr-xr-x@ 1 alice  staff  1024 Oct 18 05:46:12 2026 a -> b"
+        .parse()
+        .unwrap();
+    assert_eq!(bsd.get_permission().mode_as_octal(), "100755");
+    assert_eq!(
+        bsd.get_access_marker(),
+        Some(AccessMarker::ExtendedAttributes)
+    );
+    assert_eq!(bsd.get_group(), "staff");
+    assert_eq!(bsd.get_time(), "Oct 18 05:46:12 2026");
+    assert_eq!(bsd.get_name(), "a -> b");
+    assert_eq!(bsd.get_target(), None);
+    let row: ListingRow = "brw-rw---- 1 root disk 8,1 18 oct. 05:46 sda1"
+        .parse()
+        .unwrap();
+    assert_eq!(row.get_size(), ListingSize::Device { major: 8, minor: 1 });
+    assert_eq!(row.get_time(), "18 oct. 05:46");
+}
+#[test]
+fn parse_listing_round_trips_and_reports_the_line() {
+    let directory = tempfile::tempdir().unwrap();
+    let root = directory.path();
+    std::fs::write(root.join("a file"), "content").unwrap();
+    std::os::unix::fs::symlink("a file", root.join("link")).unwrap();
+    let listing = LongListing::new().all(true).list(root).unwrap();
+    assert_eq!(listing.to_string().parse::<Listing>().unwrap(), listing);
+    let listing = LongListing::new()
+        .time_style(TimeStyle::FullIso)
+        .list(root)
+        .unwrap();
+    assert_eq!(listing.to_string().parse::<Listing>().unwrap(), listing);
+
+    let invalid = |text: &str| match text.parse::<Listing>() {
+        Err(FullPermissionError::InvalidLine { line, reason, .. }) => (line, reason),
+        result => panic!("expected an invalid line, got {result:?}"),
+    };
+    assert_eq!(
+        invalid("total 4\n\n-rw-r--r-x 1 root root 10 Oct 18 05:46"),
+        (3, "expected a name at position 38".to_string())
+    );
+    assert_eq!(
+        invalid("-rw-r--r-- 1 root root ten Oct 18 05:46 a"),
+        (1, "the size is not a number at position 23".to_string())
+    );
+    assert_eq!(invalid("-rw-r--r-- 1 root root 10 yesterday a").0, 1);
+    assert_eq!(
+        invalid("-rw-r--r-- 1 root root 10 Oct 18 05:46 a\ntotal 4"),
+        (
+            2,
+            "invalid character `t` at position 0 of `total`, expected one of `-dlcbpsDwP` \
+             at position 0"
+                .to_string()
+        )
+    );
+    assert!(matches!(
+        "-rw-r--r-- 1".parse::<ListingRow>(),
+        Err(FullPermissionError::InvalidLine { line: 1, .. })
+    ));
+}
+//=======================================================
+#[test]
+fn stat_format_renders_like_gnu_stat() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("data");
+    std::fs::write(&path, "content").unwrap();
+    FullPermission::new(0o102750)
+        .unwrap()
+        .apply_to(&path)
+        .unwrap();
+    let metadata = path.metadata().unwrap();
+
+    let format = StatFormat::new("%a %A %f %F %h %t,%T").unwrap();
+    assert_eq!(
+        format.render(&path).unwrap(),
+        "2750 -rwxr-s--- 85e8 regular file 1 0,0"
+    );
+    let format: StatFormat = "[%#a|%06a|%-5a|%#f|%3h|%.0t|%.3A|%%|%u|%i]"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        format.render(&path).unwrap(),
+        format!(
+            "[02750|002750|2750 |0x85e8|  1||-rw|%|{}|{}]",
+            metadata.uid(),
+            metadata.ino()
+        )
+    );
+    let format = StatFormat::try_from("%n|%F").unwrap();
+    assert_eq!(
+        format
+            .render_metadata("name", &directory.path().metadata().unwrap())
+            .unwrap(),
+        "name|directory"
+    );
+    let format = StatFormat::new("%F %t %T %U:%G").unwrap();
+    assert_eq!(
+        format.render("/dev/null").unwrap(),
+        "character special file 1 3 root:root"
+    );
+    assert_eq!(format.to_string(), "%F %t %T %U:%G");
+}
+#[test]
+fn stat_format_quotes_names_and_rejects_unknown_directives() {
+    let directory = tempfile::tempdir().unwrap();
+    let root = directory.path();
+    for name in ["plain", "it's", "a b", "tab\there", "x\u{1b}y", "é"] {
+        std::fs::write(root.join(name), "").unwrap();
+    }
+    std::os::unix::fs::symlink("it's", root.join("link")).unwrap();
+    let format = StatFormat::new("%N").unwrap();
+    let quoted =
+        |name: &str| format.render_metadata(name, &root.join(name).symlink_metadata().unwrap());
+    assert_eq!(quoted("plain").unwrap(), "'plain'");
+    assert_eq!(quoted("it's").unwrap(), "\"it's\"");
+    assert_eq!(quoted("a b").unwrap(), "'a b'");
+    assert_eq!(quoted("tab\there").unwrap(), "'tab'$'\\t''here'");
+    assert_eq!(quoted("x\u{1b}y").unwrap(), "'x'$'\\033''y'");
+    assert_eq!(quoted("é").unwrap(), "''$'\\303\\251'");
+    let link = root.join("link");
+    let rendered = format.render_metadata(&link, &link.symlink_metadata().unwrap());
+    assert!(rendered.unwrap().ends_with("/link' -> \"it's\""));
+    // without a plain `%N` the names are written as they are
+    let format = StatFormat::new("%-6N|").unwrap();
+    let rendered = format.render_metadata("a b", &root.join("a b").metadata().unwrap());
+    assert_eq!(rendered.unwrap(), "a b   |");
+    assert_eq!(
+        StatFormat::new("%A")
+            .unwrap()
+            .render_following(&link)
+            .unwrap(),
+        "-rw-r--r--"
+    );
+
+    let error = StatFormat::new("%a %s").unwrap_err().to_string();
+    assert_eq!(
+        error,
+        "invalid character `s` at position 4 of `%a %s`, expected one of `aAfFuUgGtThinN`"
+    );
+    assert!(StatFormat::new("%5%").is_err());
+    assert!(StatFormat::new("%-").is_err());
+    let error = StatFormat::new("%99999999999a").unwrap_err();
+    assert!(matches!(
+        error,
+        FullPermissionError::InvalidFormat { position: 1, .. }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "the format `%99999999999a` is not valid at position 1: the width is larger than \
+         2147483647"
+    );
+    assert!(StatFormat::new("%.2147483648a").is_err());
+    assert!(StatFormat::new("%2147483647a").is_ok());
+    assert_eq!(
+        StatFormat::new("100%").unwrap().render(root).unwrap(),
+        "100%"
+    );
+}
+//=======================================================
+#[test]
+fn find_perm_exact_all_of_and_any_of() {
+    let exact: FindPerm = "644".parse().unwrap();
+    assert_eq!(exact.get_kind(), PermMatch::Exact);
+    assert!(exact.matches_mode(0o100644));
+    assert!(!exact.matches_mode(0o104644));
+    assert!(!exact.matches_mode(0o100664));
+
+    let all_of: FindPerm = "-u+w,g+w".parse().unwrap();
+    assert_eq!(all_of.get_bits(FileType::Regular), 0o220);
+    assert!(all_of.matches("-rw-rw-r--".parse().unwrap()));
+    assert!(all_of.matches("drwxrwsrwt".parse().unwrap()));
+    assert!(!all_of.matches("-rw-r--rw-".parse().unwrap()));
+
+    let any_of: FindPerm = "/u+s,g+s".parse().unwrap();
+    assert_eq!(any_of.get_bits(FileType::Regular), 0o6000);
+    assert!(any_of.matches_mode(0o102755));
+    assert!(!any_of.matches_mode(0o101777));
+    assert!("/000".parse::<FindPerm>().unwrap().matches_mode(0o100000));
+    assert!("-000".parse::<FindPerm>().unwrap().matches_mode(0o100000));
+
+    // `X` only gives the execute bits to directories
+    let executable: FindPerm = "-a+X".parse().unwrap();
+    assert_eq!(executable.get_bits(FileType::Regular), 0);
+    assert_eq!(executable.get_bits(FileType::Directory), 0o111);
+    assert!(executable.matches_mode(0o100600));
+    assert!(!executable.matches_mode(0o040700));
+    // the deprecated `+mode` is an exact symbolic operand
+    let plus: FindPerm = "+w".parse().unwrap();
+    assert_eq!(plus.get_kind(), PermMatch::Exact);
+    assert!(plus.matches_mode(0o100222));
+
+    let octal = FindPerm::new(PermMatch::AllOf, 0o4000).unwrap();
+    assert_eq!(octal.to_string(), "-4000");
+    assert_eq!(any_of.to_string(), "/u+s,g+s");
+    assert!(FindPerm::new(PermMatch::Exact, 0o10000).is_err());
+    assert!(matches!(
+        "-0789".parse::<FindPerm>(),
+        Err(FullPermissionError::InvalidNumber { .. })
+    ));
+    assert!("/u+q".parse::<FindPerm>().is_err());
+}
+#[test]
+fn find_perm_walks_the_tree() {
+    let directory = chmod_tree();
+    let root = directory.path();
+    FullPermission::new(0o100666)
+        .unwrap()
+        .apply_to(root.join("README"))
+        .unwrap();
+    FullPermission::new(0o104755)
+        .unwrap()
+        .apply_to(root.join("bin/tool"))
+        .unwrap();
+    FullPermission::new(0o40777)
+        .unwrap()
+        .apply_to(root.join(".git/objects"))
+        .unwrap();
+    std::os::unix::fs::symlink("README", root.join("link")).unwrap();
+
+    let relative = |test: &str| -> Vec<String> {
+        let test: FindPerm = test.parse().unwrap();
+        test.find(root)
+            .map(|path| {
+                let path = path.unwrap();
+                path.strip_prefix(root).unwrap().display().to_string()
+            })
+            .collect()
+    };
+    assert_eq!(relative("-o+w"), [".git/objects", "README", "link"]);
+    assert_eq!(
+        relative("/o+w,u+s"),
+        [".git/objects", "README", "bin/tool", "link"]
+    );
+    assert_eq!(relative("-4000"), ["bin/tool"]);
+    assert_eq!(relative("755"), ["", ".git", "bin"]);
+    let missing: FindPerm = "/777".parse().unwrap();
+    let mut results = missing.find(root.join("missing"));
+    assert!(matches!(
+        results.next(),
+        Some(Err(FullPermissionError::Io { .. }))
+    ));
+    assert!(results.next().is_none());
+}
+//=======================================================
+#[test]
+fn perm_pattern_compiles_masks() {
+    let pattern: PermPattern = "-rw?r??---".parse().unwrap();
+    assert_eq!(pattern.get_masks(), [(0o171647, 0o100640)]);
+    assert!(pattern.matches_mode(0o100750));
+    assert!(pattern.matches_mode(0o104640));
+    assert!(pattern.matches_mode(0o640));
+    assert!(!pattern.matches_mode(0o040640));
+    assert!(!pattern.matches_mode(0o100644));
+    assert_eq!(pattern.to_string(), "-rw?r??---");
+
+    // an execute slot that allows three of its four states needs two pairs
+    let pattern: PermPattern = "???[!s]??????".parse().unwrap();
+    assert_eq!(pattern.get_masks(), [(0o4000, 0), (0o100, 0)]);
+    assert!(pattern.matches("-rwSr--r--".parse().unwrap()));
+    assert!(pattern.matches("-rwxr--r--".parse().unwrap()));
+    assert!(!pattern.matches("-rwsr--r--".parse().unwrap()));
+
+    let pattern: PermPattern = "[dl]???????w[tT]".parse().unwrap();
+    assert_eq!(pattern.get_masks().len(), 2);
+    assert!(pattern.matches("drwxrwxrwt".parse().unwrap()));
+    assert!(pattern.matches("lrwx----wT".parse().unwrap()));
+    assert!(!pattern.matches("drwxrwxrwx".parse().unwrap()));
+    assert!(!pattern.matches("-rwxrwxrwt".parse().unwrap()));
+}
+#[test]
+fn perm_pattern_rejects_invalid_slots() {
+    let position = |pattern: &str| match pattern.parse::<PermPattern>() {
+        Err(FullPermissionError::InvalidSymbol {
+            position, found, ..
+        }) => (position, found),
+        other => panic!("{pattern} gave {other:?}"),
+    };
+    assert_eq!(position("-rw?r??--s"), (9, Some('s')));
+    assert_eq!(position("-rw?r??-w[s-]"), (10, Some('s')));
+    assert_eq!(position("-r[]?r??---"), (3, Some(']')));
+    assert_eq!(position("-r[!-w]?r??---"), (6, Some(']')));
+    assert_eq!(position("-rw[sx"), (6, None));
+    assert_eq!(position("-rw?r?"), (6, None));
+    assert_eq!(position("-rw?r??----"), (10, Some('-')));
+    assert_eq!(position("xrw?r??---"), (0, Some('x')));
+    assert_eq!(
+        "rw-r--r-s".parse::<PermPattern>().unwrap_err().to_string(),
+        "invalid character `s` at position 8 of `rw-r--r-s`, expected one of `-xTt`"
+    );
+}
+//=======================================================
+#[test]
+fn access_follows_the_owner_group_other_precedence() {
+    let file = OwnedPermission::new("-r--rw----".parse().unwrap(), 1000, 100);
+    let owner = Identity::new(1000, 100);
+    let member = Identity::new(1001, 1001).with_groups([50, 100]);
+    let stranger = Identity::new(1002, 1002).with_groups([50]);
+
+    assert_eq!(owner.class_for(&file), Class::User);
+    assert_eq!(member.class_for(&file), Class::Group);
+    assert_eq!(stranger.class_for(&file), Class::Other);
+    assert_eq!(owner.granted(&file), PermSet::READ);
+    assert!(!owner.can(&file, Perm::Write));
+    assert!(member.can(&file, PermSet::READ | PermSet::WRITE));
+    assert!(!member.can(&file, "rwx".parse::<PermSet>().unwrap()));
+    assert_eq!(stranger.granted(&file), PermSet::empty());
+    assert!(stranger.can(&file, PermSet::empty()));
+    // the primary group counts like the supplementary ones
+    assert!(Identity::new(1003, 100).can(&file, Perm::Write));
+
+    let root = Identity::new(0, 0);
+    assert!(root.is_root());
+    assert_eq!(root.granted(&file), PermSet::READ | PermSet::WRITE);
+    let script = OwnedPermission::new("----------".parse().unwrap(), 1000, 100);
+    assert!(!root.can(&script, Perm::Execute));
+    let script = OwnedPermission::new("-------r-x".parse().unwrap(), 1000, 100);
+    assert!(root.can(&script, Perm::Execute));
+    let directory = OwnedPermission::new("d---------".parse().unwrap(), 1000, 100);
+    assert!(root.can(&directory, Perm::Execute));
+}
+#[test]
+fn access_reads_the_process_and_the_file() {
+    let identity = Identity::current().unwrap();
+    let process = Identity::of_process(std::process::id()).unwrap();
+    assert_eq!(identity, process);
+
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join(FILE);
+    std::fs::write(&path, "").unwrap();
+    FullPermission::new(0o100640)
+        .unwrap()
+        .apply_to(&path)
+        .unwrap();
+    let file = OwnedPermission::of_path(&path).unwrap();
+    assert_eq!(file.get_permission().mode_as_octal(), "100640");
+    assert_eq!(file.get_uid(), identity.get_uid());
+    assert_eq!(identity.class_for(&file), Class::User);
+    assert!(identity.can(&file, PermSet::READ | PermSet::WRITE));
+    assert!(matches!(
+        OwnedPermission::of_path(directory.path().join("missing")),
+        Err(FullPermissionError::Io { .. })
+    ));
+}
+//=======================================================
+#[test]
+fn operation_checks_name_the_blocking_bit() {
+    let owned = |permission: &str, uid: u32, gid: u32| {
+        OwnedPermission::new(permission.parse().unwrap(), uid, gid)
+    };
+    let reason = |verdict: Verdict| {
+        assert!(!verdict.is_allowed());
+        verdict.get_reason().to_string()
+    };
+    let projects = owned("drwxr-x---", 1000, 100);
+    let member = Identity::new(1001, 1001).with_groups([100]);
+    let owner = Identity::new(1000, 100);
+
+    assert!(owner
+        .check(&Operation::Create {
+            directory: projects
+        })
+        .is_allowed());
+    assert_eq!(
+        reason(member.check(&Operation::Create {
+            directory: projects
+        })),
+        "the directory `drwxr-x---` lacks the group write bit (`g+w`)"
+    );
+    assert_eq!(
+        reason(Identity::new(1002, 1002).check(&Operation::Create {
+            directory: projects
+        })),
+        "the directory `drwxr-x---` lacks the other search bit (`o+x`)"
+    );
+    let file = owned("-rw-r--r--", 1000, 100);
+    assert_eq!(
+        reason(owner.check(&Operation::Create { directory: file })),
+        "the parent `-rw-r--r--` is not a directory"
+    );
+    // the permission of the file itself does not matter to delete it
+    let shared = owned("drwxrwx---", 1000, 100);
+    let locked = owned("----------", 1000, 100);
+    let verdict = member.check(&Operation::Delete {
+        directory: shared,
+        file: locked,
+    });
+    assert!(verdict.is_allowed());
+    assert!(verdict.to_string().starts_with("allowed: "));
+    assert!(Identity::new(0, 0)
+        .check(&Operation::Delete {
+            directory: owned("d---------", 1000, 100),
+            file: locked,
+        })
+        .is_allowed());
+}
+#[test]
+fn operation_checks_sticky_directories_and_scripts() {
+    let owned = |permission: &str, uid: u32, gid: u32| {
+        OwnedPermission::new(permission.parse().unwrap(), uid, gid)
+    };
+    let tmp = owned("drwxrwxrwt", 0, 0);
+    let mine = owned("-rw-------", 1001, 1001);
+    let theirs = owned("-rw-rw-rw-", 1000, 1000);
+    let user = Identity::new(1001, 1001);
+
+    let delete = |file| Operation::Delete {
+        directory: tmp,
+        file,
+    };
+    assert!(user.check(&delete(mine)).is_allowed());
+    assert!(!user.check(&delete(theirs)).is_allowed());
+    assert!(Identity::new(0, 0).check(&delete(theirs)).is_allowed());
+    // the owner of the directory may delete any entry
+    let home = owned("drwxrwxrwt", 1001, 1001);
+    assert!(user
+        .check(&Operation::Delete {
+            directory: home,
+            file: theirs,
+        })
+        .is_allowed());
+
+    let rename = |replaced| Operation::Rename {
+        directory: tmp,
+        file: mine,
+        replaced,
+    };
+    assert!(user.check(&rename(None)).is_allowed());
+    assert_eq!(
+        user.check(&rename(Some(theirs))).to_string(),
+        "denied: the directory `drwxrwxrwt` has the sticky bit (`+t`) and uid 1001 owns \
+         neither the file nor the directory"
+    );
+
+    let binary = owned("-rwx--x--x", 1000, 1000);
+    assert!(user
+        .check(&Operation::Execute { file: binary })
+        .is_allowed());
+    assert_eq!(
+        user.check(&Operation::ExecuteScript { file: binary })
+            .get_reason(),
+        "the file `-rwx--x--x` lacks the other read bit (`o+r`)"
+    );
+    let script = owned("-rw-r--r--", 1000, 1000);
+    assert_eq!(
+        user.check(&Operation::ExecuteScript { file: script })
+            .get_reason(),
+        "the file `-rw-r--r--` lacks the other execute bit (`o+x`)"
+    );
+    assert_eq!(
+        Identity::new(0, 0)
+            .check(&Operation::Execute { file: script })
+            .get_reason(),
+        "the file `-rw-r--r--` has no execute bit (`a+x`) in any class, root needs one"
+    );
+    assert_eq!(
+        user.check(&Operation::Execute { file: tmp }).get_reason(),
+        "the file `drwxrwxrwt` is not a regular file"
+    );
+}